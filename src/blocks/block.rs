@@ -0,0 +1,72 @@
+use crate::blocks::BlockModel;
+use glam::Vec3;
+use serde::Deserialize;
+
+pub type BlockId = u8;
+
+pub const AIR: BlockId = 0;
+
+// Як малюються грані блока
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderLayer {
+    Opaque,
+    Cutout,      // Непрозорі пікселі текстури, решта відкидається (листя)
+    Translucent, // Змішування з тим, що позаду, з прозорістю opacity (вода, скло)
+}
+
+// Опис типу блока. Грані в textures ідуть у порядку face_id мешера:
+// верх, низ, право, ліво, зад, перед
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub id: BlockId,
+    pub name: String,
+    pub solid: bool,       // Має колізію
+    pub transparent: bool, // Не закриває грані сусідів
+    pub fluid: bool,
+    pub model: BlockModel,
+    pub render_layer: RenderLayer,
+    pub opacity: f32, // 0..=1, лише для RenderLayer::Translucent
+    pub textures: [u16; 6],
+    pub color: Vec3,
+    pub light_emission: u8, // 0..=15
+    pub hardness: f32,
+}
+
+impl Block {
+    pub fn new(id: BlockId, name: &str) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            solid: true,
+            transparent: false,
+            fluid: false,
+            model: BlockModel::Cube,
+            render_layer: RenderLayer::Opaque,
+            opacity: 1.0,
+            textures: [0; 6],
+            color: Vec3::ONE,
+            light_emission: 0,
+            hardness: 1.0,
+        }
+    }
+
+    pub fn air() -> Self {
+        Self {
+            solid: false,
+            transparent: true,
+            hardness: 0.0,
+            ..Self::new(AIR, "air")
+        }
+    }
+
+    pub fn is_air(&self) -> bool {
+        self.id == AIR
+    }
+
+    // Чи ховає цей блок грані сусідніх блоків з усіх боків і затіняє кути.
+    // Неповні моделі ховають лише ті боки, які закривають (BlockModel::covers)
+    pub fn is_opaque(&self) -> bool {
+        !self.transparent && self.model == BlockModel::Cube
+    }
+}
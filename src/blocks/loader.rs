@@ -0,0 +1,236 @@
+use crate::blocks::{AIR, Block, BlockId, BlockModel, BlockRegistry, MAX_TEXTURES, RenderLayer};
+use glam::Vec3;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use toml::Spanned;
+
+// Помилка у файлі блоків з позицією (рядок і стовпчик від 1), якщо вона відома
+#[derive(Debug, Clone)]
+pub struct BlockFileError {
+    pub path: PathBuf,
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl fmt::Display for BlockFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some((line, column)) => {
+                write!(f, "{}:{}:{}: {}", self.path.display(), line, column, self.message)
+            }
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+impl std::error::Error for BlockFileError {}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BlockFile {
+    #[serde(rename = "block", default)]
+    blocks: Vec<BlockEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BlockEntry {
+    id: Spanned<BlockId>,
+    name: Spanned<String>,
+    #[serde(default = "default_solid")]
+    solid: bool,
+    #[serde(default)]
+    transparent: bool,
+    #[serde(default)]
+    fluid: bool,
+    #[serde(default)]
+    model: BlockModel,
+    // Без явного значення: cutout для прозорих блоків, opaque для решти
+    render_layer: Option<RenderLayer>,
+    opacity: Option<Spanned<f32>>,
+    textures: Spanned<FaceTextures>,
+    #[serde(default = "default_color")]
+    color: [f32; 3],
+    light_emission: Option<Spanned<u8>>,
+    #[serde(default = "default_hardness")]
+    hardness: f32,
+}
+
+// all — для всіх граней; side — для чотирьох бічних; top/bottom мають пріоритет
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FaceTextures {
+    all: Option<Spanned<String>>,
+    side: Option<Spanned<String>>,
+    top: Option<Spanned<String>>,
+    bottom: Option<Spanned<String>>,
+}
+
+const FACE_NAMES: [&str; 6] = ["top", "bottom", "right", "left", "back", "front"];
+
+fn default_solid() -> bool {
+    true
+}
+
+fn default_color() -> [f32; 3] {
+    [1.0, 1.0, 1.0]
+}
+
+fn default_hardness() -> f32 {
+    1.0
+}
+
+// Завантажує блоки з TOML-файлу. Текстури шукаються як <ім'я>.png поруч із файлом.
+// Повертає всі знайдені помилки, а не лише першу.
+pub fn load_blocks(path: &Path) -> Result<BlockRegistry, Vec<BlockFileError>> {
+    let source = std::fs::read_to_string(path).map_err(|err| {
+        vec![BlockFileError {
+            path: path.to_path_buf(),
+            position: None,
+            message: err.to_string(),
+        }]
+    })?;
+    let texture_dir = path.parent().unwrap_or(Path::new("."));
+    parse_blocks(&source, path, |name| {
+        texture_dir.join(format!("{}.png", name)).is_file()
+    })
+}
+
+pub fn parse_blocks(
+    source: &str,
+    path: &Path,
+    texture_exists: impl Fn(&str) -> bool,
+) -> Result<BlockRegistry, Vec<BlockFileError>> {
+    let error = |span: Option<Range<usize>>, message: String| BlockFileError {
+        path: path.to_path_buf(),
+        position: span.map(|span| line_column(source, span.start)),
+        message,
+    };
+
+    let file: BlockFile = toml::from_str(source)
+        .map_err(|err| vec![error(err.span(), err.message().to_string())])?;
+
+    let mut errors = Vec::new();
+    let mut registry = BlockRegistry::new();
+    let mut ids: HashMap<BlockId, String> = HashMap::new();
+    let mut names: HashMap<String, BlockId> = HashMap::new();
+
+    for entry in &file.blocks {
+        let id = *entry.id.get_ref();
+        let name = entry.name.get_ref();
+
+        if id == AIR {
+            errors.push(error(Some(entry.id.span()), "id 0 is reserved for air".to_string()));
+        } else if let Some(other) = ids.get(&id) {
+            errors.push(error(
+                Some(entry.id.span()),
+                format!("duplicate block id {} (already used by \"{}\")", id, other),
+            ));
+        }
+        if name == "air" || names.contains_key(name) {
+            errors.push(error(
+                Some(entry.name.span()),
+                format!("duplicate block name \"{}\"", name),
+            ));
+        }
+        let light_emission = entry.light_emission.as_ref();
+        if let Some(light) = light_emission.filter(|light| *light.get_ref() > 15) {
+            errors.push(error(
+                Some(light.span()),
+                "light_emission must be in 0..=15".to_string(),
+            ));
+        }
+        let opacity = entry.opacity.as_ref();
+        if let Some(opacity) = opacity.filter(|opacity| !(0.0..=1.0).contains(opacity.get_ref())) {
+            errors.push(error(
+                Some(opacity.span()),
+                "opacity must be in 0..=1".to_string(),
+            ));
+        }
+
+        let faces = entry.textures.get_ref();
+        let mut textures = [0u16; 6];
+        let mut missing_faces = Vec::new();
+        let mut missing_textures: Vec<&Spanned<String>> = Vec::new();
+        // Порядок граней як у мешері: верх, низ, право, ліво, зад, перед
+        for (face, texture) in textures.iter_mut().enumerate() {
+            let specific = match face {
+                0 => faces.top.as_ref(),
+                1 => faces.bottom.as_ref(),
+                _ => faces.side.as_ref(),
+            };
+            let Some(texture_name) = specific.or(faces.all.as_ref()) else {
+                missing_faces.push(FACE_NAMES[face]);
+                continue;
+            };
+            if !texture_exists(texture_name.get_ref()) {
+                if !missing_textures.iter().any(|missing| missing.span() == texture_name.span()) {
+                    missing_textures.push(texture_name);
+                }
+                continue;
+            }
+            *texture = registry.texture_index(texture_name.get_ref());
+        }
+        if !missing_faces.is_empty() {
+            errors.push(error(
+                Some(entry.textures.span()),
+                format!("block \"{}\" has no texture for {}", name, missing_faces.join(", ")),
+            ));
+        }
+        for texture_name in missing_textures {
+            errors.push(error(
+                Some(texture_name.span()),
+                format!("missing texture \"{}\"", texture_name.get_ref()),
+            ));
+        }
+
+        ids.insert(id, name.clone());
+        names.insert(name.clone(), id);
+        registry.register(Block {
+            id,
+            name: name.clone(),
+            solid: entry.solid,
+            transparent: entry.transparent,
+            fluid: entry.fluid,
+            model: entry.model,
+            render_layer: entry.render_layer.unwrap_or(if entry.transparent {
+                RenderLayer::Cutout
+            } else {
+                RenderLayer::Opaque
+            }),
+            opacity: opacity.map_or(1.0, |opacity| *opacity.get_ref()),
+            textures,
+            color: Vec3::from_array(entry.color),
+            light_emission: light_emission.map_or(0, |light| *light.get_ref()),
+            hardness: entry.hardness,
+        });
+    }
+
+    // Номер текстури займає 7 бітів у вершині чанка
+    if registry.texture_names().len() > MAX_TEXTURES {
+        errors.push(error(
+            None,
+            format!(
+                "too many textures ({}), at most {} are supported",
+                registry.texture_names().len(),
+                MAX_TEXTURES
+            ),
+        ));
+    }
+
+    if errors.is_empty() {
+        Ok(registry)
+    } else {
+        Err(errors)
+    }
+}
+
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    (line, column)
+}
@@ -0,0 +1,9 @@
+pub mod block;
+pub mod loader;
+pub mod model;
+pub mod registry;
+
+pub use block::{AIR, Block, BlockId, RenderLayer};
+pub use loader::{BlockFileError, load_blocks};
+pub use model::{BlockModel, MODEL_UNITS, ModelBox};
+pub use registry::{BlockRegistry, MAX_TEXTURES};
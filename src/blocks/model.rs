@@ -0,0 +1,76 @@
+use serde::Deserialize;
+
+// Координати моделей — у частках блока: 0..=MODEL_UNITS уздовж кожної осі
+pub const MODEL_UNITS: i32 = 16;
+
+// Паралелепіпед моделі від min до max
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelBox {
+    pub min: [i32; 3],
+    pub max: [i32; 3],
+}
+
+const FULL: i32 = MODEL_UNITS;
+const HALF: i32 = MODEL_UNITS / 2;
+
+const CUBE: [ModelBox; 1] = [ModelBox {
+    min: [0, 0, 0],
+    max: [FULL, FULL, FULL],
+}];
+const SLAB: [ModelBox; 1] = [ModelBox {
+    min: [0, 0, 0],
+    max: [FULL, HALF, FULL],
+}];
+// Сходинка піднімається в бік +Z
+const STAIRS: [ModelBox; 2] = [
+    ModelBox {
+        min: [0, 0, 0],
+        max: [FULL, HALF, FULL],
+    },
+    ModelBox {
+        min: [0, HALF, HALF],
+        max: [FULL, FULL, FULL],
+    },
+];
+// Тонка стінка вздовж X посередині блока
+const PANE: [ModelBox; 1] = [ModelBox {
+    min: [0, 0, HALF - 1],
+    max: [FULL, FULL, HALF + 1],
+}];
+
+// Геометрія блока. Стану блока (повороту) поки немає, тож сходинки й панелі
+// мають одну орієнтацію
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockModel {
+    #[default]
+    Cube,
+    Slab,   // Нижня половина блока
+    Stairs, // Нижня половина і задня верхня чверть
+    Cross,  // Дві двобічні діагональні площини (рослини)
+    Pane,   // Тонка стінка (скляна панель)
+}
+
+impl BlockModel {
+    // Паралелепіпеди моделі; у Cross їх немає, її площини будує мешер
+    pub fn boxes(self) -> &'static [ModelBox] {
+        match self {
+            BlockModel::Cube => &CUBE,
+            BlockModel::Slab => &SLAB,
+            BlockModel::Stairs => &STAIRS,
+            BlockModel::Cross => &[],
+            BlockModel::Pane => &PANE,
+        }
+    }
+
+    // Чи закриває модель повністю свій бік face_id (порядок граней як у мешері);
+    // лише такий бік ховає грань сусіда
+    pub fn covers(self, face_id: usize) -> bool {
+        match self {
+            BlockModel::Cube => true,
+            BlockModel::Slab => face_id == 1,
+            BlockModel::Stairs => face_id == 1 || face_id == 5,
+            BlockModel::Cross | BlockModel::Pane => false,
+        }
+    }
+}
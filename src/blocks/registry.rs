@@ -0,0 +1,115 @@
+use crate::blocks::block::{AIR, Block, BlockId, RenderLayer};
+use crate::blocks::loader::parse_blocks;
+use crate::blocks::model::BlockModel;
+use glam::Vec4;
+use std::path::Path;
+
+const MAX_BLOCKS: usize = BlockId::MAX as usize + 1;
+// Скільки різних текстур уміщує вершина чанка (7 біт, див. ChunkVertex)
+pub const MAX_TEXTURES: usize = 128;
+
+// Єдине джерело правди про типи блоків для генерації, мешингу, фізики і шейдера
+#[derive(Debug, Clone)]
+pub struct BlockRegistry {
+    blocks: Vec<Option<Block>>,
+    texture_names: Vec<String>, // Індекс = номер текстури в Block::textures
+}
+
+// Вбудований набір — той самий blocks.toml, що вкомпільований у бібліотеку, тож
+// запасний реєстр не розходиться з файлом
+impl Default for BlockRegistry {
+    fn default() -> Self {
+        parse_blocks(
+            include_str!("../assets/blocks.toml"),
+            Path::new("blocks.toml"),
+            |_| true,
+        )
+        .expect("built-in blocks.toml is valid")
+    }
+}
+
+impl BlockRegistry {
+    // Порожній реєстр, де є лише повітря
+    pub fn new() -> Self {
+        let mut blocks = vec![None; MAX_BLOCKS];
+        blocks[AIR as usize] = Some(Block::air());
+        Self {
+            blocks,
+            texture_names: Vec::new(),
+        }
+    }
+
+    // Замінює наявне визначення з тим самим id
+    pub fn register(&mut self, block: Block) {
+        let id = block.id as usize;
+        self.blocks[id] = Some(block);
+    }
+
+    pub fn get(&self, id: BlockId) -> Option<&Block> {
+        self.blocks[id as usize].as_ref()
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&Block> {
+        self.iter().find(|block| block.name == name)
+    }
+
+    pub fn id(&self, name: &str) -> Option<BlockId> {
+        self.by_name(name).map(|block| block.id)
+    }
+
+    // Індекс текстури за іменем; нове ім'я додається в кінець
+    pub fn texture_index(&mut self, name: &str) -> u16 {
+        match self.texture_names.iter().position(|texture| texture == name) {
+            Some(index) => index as u16,
+            None => {
+                self.texture_names.push(name.to_string());
+                (self.texture_names.len() - 1) as u16
+            }
+        }
+    }
+
+    pub fn texture_names(&self) -> &[String] {
+        &self.texture_names
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Block> {
+        self.blocks.iter().flatten()
+    }
+
+    // Невідомі id вважаємо суцільними, щоб не відкривати зайвих граней
+    pub fn is_opaque(&self, id: BlockId) -> bool {
+        self.get(id).is_none_or(Block::is_opaque)
+    }
+
+    pub fn is_solid(&self, id: BlockId) -> bool {
+        self.get(id).is_none_or(|block| block.solid)
+    }
+
+    pub fn model(&self, id: BlockId) -> BlockModel {
+        self.get(id).map_or(BlockModel::Cube, |block| block.model)
+    }
+
+    // Шар масиву текстур для грані face_id; невідомі блоки беруть першу текстуру
+    pub fn face_texture(&self, id: BlockId, face_id: usize) -> u16 {
+        self.get(id).map_or(0, |block| block.textures[face_id])
+    }
+
+    pub fn render_layer(&self, id: BlockId) -> RenderLayer {
+        self.get(id).map_or(RenderLayer::Opaque, |block| block.render_layer)
+    }
+
+    // Кольори для uniform-масиву шейдера, індекс = id блока. В альфі — opacity
+    // для напівпрозорих блоків і 1 для решти
+    pub fn colors(&self) -> Vec<Vec4> {
+        self.blocks
+            .iter()
+            .map(|block| match block {
+                Some(block) if block.render_layer == RenderLayer::Translucent => {
+                    block.color.extend(block.opacity)
+                }
+                Some(block) => block.color.extend(1.0),
+                None => Vec4::new(1.0, 0.0, 1.0, 1.0),
+            })
+            .collect()
+    }
+}
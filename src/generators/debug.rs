@@ -0,0 +1,52 @@
+use crate::blocks::{AIR, BlockId, BlockRegistry};
+use crate::generators::WorldGenerator;
+use crate::settings::{CHUNK_SIZE, CHUNK_VOL};
+use glam::IVec3;
+
+// Сітка з усіх зареєстрованих блоків на висоті y = 0, через клітинку,
+// починаючи з x = 0, z = 0
+pub struct DebugGenerator {
+    blocks: Vec<BlockId>,
+    columns: i32,
+}
+
+impl DebugGenerator {
+    pub fn new(registry: &BlockRegistry) -> Self {
+        let blocks: Vec<BlockId> = registry
+            .iter()
+            .filter(|block| !block.is_air())
+            .map(|block| block.id)
+            .collect();
+        let columns = (blocks.len() as f32).sqrt().ceil().max(1.0) as i32;
+        Self { blocks, columns }
+    }
+
+    fn block_at(&self, wx: i32, wz: i32) -> BlockId {
+        if wx < 0 || wz < 0 || wx % 2 != 0 || wz % 2 != 0 {
+            return AIR;
+        }
+        let (column, row) = (wx / 2, wz / 2);
+        if column >= self.columns {
+            return AIR;
+        }
+        let index = (column + row * self.columns) as usize;
+        self.blocks.get(index).copied().unwrap_or(AIR)
+    }
+}
+
+impl WorldGenerator for DebugGenerator {
+    fn generate(&self, chunk_pos: IVec3) -> Vec<BlockId> {
+        let mut voxels = vec![AIR; CHUNK_VOL as usize];
+        if chunk_pos.y != 0 {
+            return voxels;
+        }
+        for x in 0..CHUNK_SIZE {
+            let wx = chunk_pos.x * CHUNK_SIZE as i32 + x as i32;
+            for z in 0..CHUNK_SIZE {
+                let wz = chunk_pos.z * CHUNK_SIZE as i32 + z as i32;
+                voxels[(x + CHUNK_SIZE * z) as usize] = self.block_at(wx, wz);
+            }
+        }
+        voxels
+    }
+}
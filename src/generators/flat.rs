@@ -0,0 +1,51 @@
+use crate::blocks::{AIR, BlockId, BlockRegistry};
+use crate::generators::WorldGenerator;
+use crate::settings::{CHUNK_AREA, CHUNK_SIZE, CHUNK_VOL};
+use glam::IVec3;
+
+// Плаский світ із шарів (блок, товщина), знизу вгору від y = 0
+pub struct FlatGenerator {
+    // Блок для кожної висоти від y = 0
+    column: Vec<BlockId>,
+}
+
+impl FlatGenerator {
+    pub fn new(layers: &[(BlockId, u32)]) -> Self {
+        let column = layers
+            .iter()
+            .flat_map(|(block_id, height)| std::iter::repeat_n(*block_id, *height as usize))
+            .collect();
+        Self { column }
+    }
+
+    // Невідомі імена блоків пропускаються з попередженням
+    pub fn from_names(layers: &[(String, u32)], registry: &BlockRegistry) -> Self {
+        let layers: Vec<(BlockId, u32)> = layers
+            .iter()
+            .filter_map(|(name, height)| match registry.id(name) {
+                Some(block_id) => Some((block_id, *height)),
+                None => {
+                    eprintln!("Flat generator: unknown block \"{}\", layer skipped", name);
+                    None
+                }
+            })
+            .collect();
+        Self::new(&layers)
+    }
+}
+
+impl WorldGenerator for FlatGenerator {
+    fn generate(&self, chunk_pos: IVec3) -> Vec<BlockId> {
+        let mut voxels = vec![AIR; CHUNK_VOL as usize];
+        for y in 0..CHUNK_SIZE {
+            let wy = chunk_pos.y * CHUNK_SIZE as i32 + y as i32;
+            let Some(&block_id) = usize::try_from(wy).ok().and_then(|wy| self.column.get(wy))
+            else {
+                continue;
+            };
+            let layer = (CHUNK_AREA * y) as usize;
+            voxels[layer..layer + CHUNK_AREA as usize].fill(block_id);
+        }
+        voxels
+    }
+}
@@ -0,0 +1,54 @@
+pub mod debug;
+pub mod flat;
+pub mod noise;
+pub mod void;
+
+pub use debug::DebugGenerator;
+pub use flat::FlatGenerator;
+pub use noise::NoiseGenerator;
+pub use void::VoidGenerator;
+
+use crate::blocks::{BlockId, BlockRegistry};
+use crate::settings::{GeneratorKind, WorldHeight};
+use glam::IVec3;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::sync::Arc;
+
+// Заповнює чанк за його позицією. Результат має залежати лише від позиції
+// (і параметрів генератора), щоб чанк після перезавантаження був тим самим.
+// Індексація як у Chunk::voxel_index: x + CHUNK_SIZE * z + CHUNK_AREA * y
+pub trait WorldGenerator: Send + Sync {
+    fn generate(&self, chunk_pos: IVec3) -> Vec<BlockId>;
+}
+
+pub fn create_generator(
+    kind: &GeneratorKind,
+    seed: u32,
+    world_height: WorldHeight,
+    registry: &BlockRegistry,
+) -> Arc<dyn WorldGenerator> {
+    match kind {
+        GeneratorKind::Noise => {
+            Arc::new(NoiseGenerator::new(seed, registry).with_world_height(world_height))
+        }
+        GeneratorKind::Flat { layers } => Arc::new(FlatGenerator::from_names(layers, registry)),
+        GeneratorKind::Void => Arc::new(VoidGenerator),
+        GeneratorKind::Debug => Arc::new(DebugGenerator::new(registry)),
+    }
+}
+
+// Генератор випадкових чисел, що залежить лише від сіда світу і позиції чанка,
+// тож чанк після перезавантаження чи в іншому порядку генерації буде тим самим
+pub fn chunk_rng(seed: u32, chunk_pos: IVec3) -> ChaCha8Rng {
+    // splitmix64: стабільний між запусками і версіями Rust, на відміну від DefaultHasher
+    let mut state = (seed as u64) << 32;
+    for coord in [chunk_pos.x, chunk_pos.y, chunk_pos.z] {
+        state ^= coord as u32 as u64;
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        state ^= state >> 31;
+    }
+    ChaCha8Rng::seed_from_u64(state)
+}
@@ -0,0 +1,76 @@
+use crate::blocks::{AIR, BlockId, BlockRegistry};
+use crate::generators::{WorldGenerator, chunk_rng};
+use crate::settings::{CHUNK_AREA, CHUNK_SIZE, CHUNK_VOL, WorldHeight};
+use glam::IVec3;
+use noise::{NoiseFn, Simplex};
+use rand::seq::IndexedRandom;
+
+// Рельєф за картою висот Simplex-шуму
+pub struct NoiseGenerator {
+    seed: u32,
+    simplex: Simplex,
+    terrain_blocks: Vec<BlockId>,
+    // Поверхня коливається в межах base_height ± amplitude блоків
+    base_height: f64,
+    amplitude: f64,
+}
+
+// Висота рельєфу для кубічних чанків, де світ не має меж
+const CUBIC_AMPLITUDE: f64 = 256.0;
+
+impl NoiseGenerator {
+    pub fn new(seed: u32, registry: &BlockRegistry) -> Self {
+        let terrain_blocks = registry
+            .iter()
+            .filter(|block| block.solid && block.is_opaque() && !block.fluid)
+            .map(|block| block.id)
+            .collect();
+        Self {
+            seed,
+            simplex: Simplex::new(seed),
+            terrain_blocks,
+            base_height: 32.0,
+            amplitude: 32.0,
+        }
+    }
+
+    // Розтягує рельєф на висоту світу: від y = 0 (або дна світу, якщо воно вище)
+    // до одного чанка під стелею, щоб верхівки гір не зрізалися
+    pub fn with_world_height(mut self, world_height: WorldHeight) -> Self {
+        let (low, high) = match world_height.block_range() {
+            Some((bottom, top)) => (bottom.max(0) as f64, (top - CHUNK_SIZE as i32) as f64),
+            None => (-CUBIC_AMPLITUDE, CUBIC_AMPLITUDE),
+        };
+        let high = high.max(low);
+        self.base_height = (low + high) / 2.0;
+        self.amplitude = (high - low) / 2.0;
+        self
+    }
+}
+
+impl WorldGenerator for NoiseGenerator {
+    fn generate(&self, chunk_pos: IVec3) -> Vec<BlockId> {
+        let mut voxels = vec![AIR; CHUNK_VOL as usize];
+        let (cx, cy, cz) = (chunk_pos.x, chunk_pos.y, chunk_pos.z);
+
+        // Поки що весь чанк заповнюється одним випадковим твердим блоком
+        let mut rng = chunk_rng(self.seed, chunk_pos);
+        let block_id = *self.terrain_blocks.choose(&mut rng).unwrap_or(&AIR);
+
+        for x in 0..CHUNK_SIZE {
+            let wx = x as f32 + cx as f32 * CHUNK_SIZE as f32;
+            for z in 0..CHUNK_SIZE {
+                let wz = z as f32 + cz as f32 * CHUNK_SIZE as f32;
+                let noise_value = self.simplex.get([wx as f64 * 0.01, wz as f64 * 0.01]);
+                let world_height = (noise_value * self.amplitude + self.base_height) as i32;
+                let local_height =
+                    (world_height - cy * CHUNK_SIZE as i32).clamp(0, CHUNK_SIZE as i32);
+
+                for y in 0..local_height as u32 {
+                    voxels[(x + CHUNK_SIZE * z + CHUNK_AREA * y) as usize] = block_id;
+                }
+            }
+        }
+        voxels
+    }
+}
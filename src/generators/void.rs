@@ -0,0 +1,13 @@
+use crate::blocks::{AIR, BlockId};
+use crate::generators::WorldGenerator;
+use crate::settings::CHUNK_VOL;
+use glam::IVec3;
+
+// Порожній світ: лише повітря
+pub struct VoidGenerator;
+
+impl WorldGenerator for VoidGenerator {
+    fn generate(&self, _chunk_pos: IVec3) -> Vec<BlockId> {
+        vec![AIR; CHUNK_VOL as usize]
+    }
+}
@@ -0,0 +1,11 @@
+// Ядро рушія: дані світу, генерація та побудова мешів без залежності від OpenGL.
+// Рендеринг живе в бінарнику (src/render) і читає готові дані звідси.
+pub mod blocks;
+pub mod camera;
+pub mod generators;
+pub mod meshes;
+pub mod settings;
+pub mod storage;
+pub mod workers;
+pub mod world;
+pub mod world_objects;
@@ -1,43 +1,48 @@
-mod camera;
-mod meshes;
 mod player;
+mod render;
 mod scene;
-mod settings;
 mod shader_program;
 mod textures;
-mod world_objects;
-mod world;
 
 use crate::player::Player;
 use crate::scene::Scene;
-use crate::settings::Settings;
 use crate::shader_program::ShaderProgram;
 use crate::textures::Textures;
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
 use sdl2::video::{GLContext, GLProfile, Window};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
+use voxel_engine::blocks::{BlockRegistry, load_blocks};
+use voxel_engine::settings::Settings;
+use voxel_engine::storage::{LEVEL_FILE, Level, load_level, save_level};
 
+// Порядок полів важливий: GPU-ресурси мають звільнитися раніше за GL-контекст
 struct VoxelEngine {
-    sdl_context: sdl2::Sdl,
-    window: Window,
-    gl_context: GLContext,
-    event_pump: sdl2::EventPump,
+    scene: Scene,
+    _textures: Textures,
+    shader_program: ShaderProgram,
+    player: Player,
+    settings: Settings,
+    level: Level,
     clock: Instant,
+    last_autosave: Instant,
     delta_time: f32,
-    time: f32,
+    time: f64, // Час гри в цьому світі, секунди
     is_running: bool,
-    settings: Settings,
-    player: Player,
-    shader_program: ShaderProgram,
-    scene: Scene,
-    textures: Textures,
     mouse_captured: bool, // Нове поле
+    event_pump: sdl2::EventPump,
+    _gl_context: GLContext,
+    window: Window,
+    sdl_context: sdl2::Sdl,
 }
 
 impl VoxelEngine {
     fn new() -> Self {
-        let settings = Settings::new();
+        let mut settings = Settings::new();
+        let world_name = std::env::args().nth(1).unwrap_or_else(|| "world".to_string());
+        let level = open_world(&mut settings, &world_name);
         let sdl_context = sdl2::init().unwrap();
         let video_subsystem = sdl_context.video().unwrap();
 
@@ -66,6 +71,7 @@ impl VoxelEngine {
             gl::Enable(gl::DEPTH_TEST);
             gl::Enable(gl::CULL_FACE);
             gl::Enable(gl::BLEND);
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
             gl::Viewport(0, 0, settings.win_res.x as i32, settings.win_res.y as i32);
         }
 
@@ -73,25 +79,29 @@ impl VoxelEngine {
         sdl_context.mouse().set_relative_mouse_mode(true); // Захоплення миші
         sdl_context.mouse().show_cursor(false); // Приховування курсора
 
-        let textures = Textures::new(&gl_context);
-        let player = Player::new(&settings);
-        let shader_program = ShaderProgram::new(&player);
-        let scene = Scene::new(&shader_program, &settings);
+        let registry = Arc::new(load_block_registry());
+        let textures = Textures::new(&gl_context, &registry);
+        let mut player = Player::new(&settings);
+        player.restore(&level.player);
+        let shader_program = ShaderProgram::new(&player, &registry);
+        let scene = Scene::new(&shader_program, &settings, registry);
         Self {
-            sdl_context,
-            window,
-            gl_context,
-            event_pump,
+            scene,
+            _textures: textures,
+            shader_program,
+            player,
+            settings,
+            time: level.game_time,
+            level,
             clock: Instant::now(),
+            last_autosave: Instant::now(),
             delta_time: 0.0,
-            time: 0.0,
             is_running: true,
-            settings,
-            player,
-            shader_program,
-            scene,
-            textures,
             mouse_captured: true, // Початковий стан: миша захоплена
+            event_pump,
+            _gl_context: gl_context,
+            window,
+            sdl_context,
         }
     }
 
@@ -104,11 +114,29 @@ impl VoxelEngine {
         let now = Instant::now();
         self.delta_time = now.duration_since(self.clock).as_secs_f32() * 1000.0; // У мілісекундах
         self.clock = now;
-        self.time = now.elapsed().as_secs_f32();
+        self.time += self.delta_time as f64 / 1000.0;
+
+        if self.last_autosave.elapsed().as_secs_f32() >= self.settings.autosave_interval {
+            self.save();
+            self.last_autosave = Instant::now();
+        }
 
         let fps = 1.0 / (self.delta_time / 1000.0); // FPS
+        // Черги стрімінгу: завантаження/генерація, мешинг, завантаження в GPU, запис
+        let stats = self.scene.stats();
         self.window
-            .set_title(&format!("Voxel Engine - {:.0} FPS", fps))
+            .set_title(&format!(
+                "Voxel Engine - {:.0} FPS | load {}+{} mesh {}+{} upload {} save {} | skipped {} empty {} buried",
+                fps,
+                stats.load_queue,
+                stats.generating,
+                stats.mesh_queue,
+                stats.meshing,
+                stats.upload_queue,
+                stats.saving,
+                stats.empty_chunks,
+                stats.buried_chunks
+            ))
             .unwrap();
     }
 
@@ -122,7 +150,7 @@ impl VoxelEngine {
             );
             gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
         }
-        self.scene.render();
+        self.scene.render(&self.player);
         self.window.gl_swap_window();
     }
 
@@ -166,6 +194,65 @@ impl VoxelEngine {
             self.update();
             self.render();
         }
+        self.save();
+        self.scene.wait_for_saves();
+    }
+
+    // Змінені чанки, час гри і стан гравця
+    fn save(&mut self) {
+        let Some(world_dir) = &self.settings.world_dir else {
+            return;
+        };
+        self.scene.save();
+        self.level.game_time = self.time;
+        self.level.player = self.player.state();
+        if let Err(err) = save_level(&world_dir.join(LEVEL_FILE), &self.level) {
+            eprintln!("Failed to save level: {}", err);
+        }
+    }
+}
+
+// Світ лежить у saves/<назва>. Параметри існуючого світу замінюють налаштування;
+// пошкоджений level.toml відкладається вбік, а світ стартує з налаштувань
+fn open_world(settings: &mut Settings, name: &str) -> Level {
+    let world_dir = PathBuf::from("saves").join(name);
+    if let Err(err) = std::fs::create_dir_all(&world_dir) {
+        eprintln!("Failed to create {}: {}", world_dir.display(), err);
+        eprintln!("The world will not be saved");
+        return Level::new(settings);
+    }
+    let level_path = world_dir.join(LEVEL_FILE);
+    settings.world_dir = Some(world_dir);
+
+    match load_level(&level_path) {
+        Ok(Some(level)) => {
+            level.apply_to(settings);
+            level
+        }
+        Ok(None) => Level::new(settings),
+        Err(err) => {
+            eprintln!("{}", err);
+            let backup = level_path.with_extension("toml.corrupt");
+            match std::fs::rename(&level_path, &backup) {
+                Ok(()) => eprintln!("Moved the damaged file to {}", backup.display()),
+                Err(err) => eprintln!("Failed to move the damaged file: {}", err),
+            }
+            Level::new(settings)
+        }
+    }
+}
+
+// Помилки у файлі блоків не зупиняють гру: виводимо їх і беремо вбудований набір
+fn load_block_registry() -> BlockRegistry {
+    match load_blocks(Path::new("src/assets/blocks.toml")) {
+        Ok(registry) => registry,
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}", error);
+            }
+            eprintln!("Using built-in block definitions");
+            BlockRegistry::default()
+        }
     }
 }
 
@@ -0,0 +1,161 @@
+use crate::blocks::{AIR, BlockId, BlockModel, BlockRegistry, MODEL_UNITS};
+use crate::meshes::chunk_mesh_builder::{
+    FACES, add_block_models, add_quad, cube_face_origin, face_ao_by, is_face_visible, layer_buffer,
+};
+use crate::settings::{CHUNK_AREA, CHUNK_SIZE, UnloadedChunkPolicy};
+use crate::world_objects::{Chunk, ChunkNeighborhood};
+use glam::IVec3;
+
+// Чанк з шаром вокселів сусідів з усіх боків; у колонці u64 це біти 0..PADDED
+const PADDED: usize = CHUNK_SIZE as usize + 2;
+
+// Дві інші осі для колонок уздовж осі normal
+const OTHER_AXES: [(usize, usize); 3] = [(1, 2), (0, 2), (0, 1)];
+
+// Колонки бітів уздовж кожної осі: columns[normal][u + PADDED * v], де u і v —
+// координати по OTHER_AXES[normal] з урахуванням шару сусідів
+struct ColumnMasks {
+    columns: [Vec<u64>; 3],
+}
+
+impl ColumnMasks {
+    fn new() -> Self {
+        Self {
+            columns: std::array::from_fn(|_| vec![0; PADDED * PADDED]),
+        }
+    }
+
+    fn set(&mut self, padded: [usize; 3]) {
+        for (normal, (u, v)) in OTHER_AXES.iter().enumerate() {
+            self.columns[normal][padded[*u] + PADDED * padded[*v]] |= 1 << padded[normal];
+        }
+    }
+
+    fn get(&self, local_pos: IVec3) -> bool {
+        let padded = (local_pos + 1).as_uvec3();
+        let column = self.columns[1][padded.x as usize + PADDED * padded.z as usize];
+        column >> padded.y & 1 != 0
+    }
+}
+
+// Такі самі грані, як у build_chunk_mesh, але видимість граней кубів шукається побітово:
+// для кожної осі колонка з 34 вокселів (чанк і по вокселю сусідів) — це u64, і грані,
+// за якими повітря чи непрозорий блок, знаходяться зсувом маски на біт. Поштучно
+// перевіряються лише грані, за якими прозорий блок або неповна модель
+pub fn build_bitmask_mesh(
+    neighborhood: &ChunkNeighborhood,
+    registry: &BlockRegistry,
+    unloaded: UnloadedChunkPolicy,
+    format_size: i32,
+) -> (Vec<u32>, Vec<u32>) {
+    let chunk = neighborhood.chunk;
+    let mut opaque = Vec::with_capacity(CHUNK_AREA as usize * 4 * 4 * format_size as usize);
+    let mut translucent = Vec::new();
+
+    // cubes — куби самого чанка, які мають грані; open — повітря, яке відкриває будь-яку
+    // грань; closed — непрозорі куби, які будь-яку грань ховають; shading — те, що
+    // затіняє кути (незавантажені чанки не затіняють)
+    let mut cubes = ColumnMasks::new();
+    let mut open = ColumnMasks::new();
+    let mut closed = ColumnMasks::new();
+    let mut shading = ColumnMasks::new();
+    let voxels = chunk.voxels.to_vec();
+    let last = PADDED - 1;
+    for y in 0..PADDED {
+        for z in 0..PADDED {
+            for x in 0..PADDED {
+                let padded = [x, y, z];
+                let border = padded.iter().any(|&c| c == 0 || c == last);
+                let local_pos = IVec3::new(x as i32, y as i32, z as i32) - 1;
+                let voxel_id = if border {
+                    neighborhood.voxel(local_pos)
+                } else {
+                    Some(voxels[Chunk::voxel_index(local_pos)])
+                };
+                match voxel_id {
+                    Some(AIR) => open.set(padded),
+                    Some(voxel_id) => {
+                        if registry.is_opaque(voxel_id) {
+                            closed.set(padded);
+                            shading.set(padded);
+                        }
+                        if !border && registry.model(voxel_id) == BlockModel::Cube {
+                            cubes.set(padded);
+                        }
+                    }
+                    None => match unloaded {
+                        UnloadedChunkPolicy::Air => open.set(padded),
+                        UnloadedChunkPolicy::Solid => closed.set(padded),
+                    },
+                }
+            }
+        }
+    }
+
+    for (face_id, face) in FACES.iter().enumerate() {
+        let (u_axis, v_axis) = OTHER_AXES[face.normal];
+        // Сусід вокселя в біті i — у біті i + 1 для грані на дальньому боці, i - 1 — на ближньому
+        let toward = |mask: u64| {
+            if face.offset == 1 {
+                mask >> 1
+            } else {
+                mask << 1
+            }
+        };
+        for v in 1..=CHUNK_SIZE as usize {
+            for u in 1..=CHUNK_SIZE as usize {
+                let column = u + PADDED * v;
+                let cube_column = cubes.columns[face.normal][column];
+                if cube_column == 0 {
+                    continue;
+                }
+                let open_column = toward(open.columns[face.normal][column]);
+                let closed_column = toward(closed.columns[face.normal][column]);
+                let mut faces = cube_column & !closed_column;
+
+                while faces != 0 {
+                    let bit = faces.trailing_zeros() as usize;
+                    faces &= faces - 1;
+
+                    let mut voxel_pos = IVec3::ZERO;
+                    voxel_pos[face.normal] = bit as i32 - 1;
+                    voxel_pos[u_axis] = u as i32 - 1;
+                    voxel_pos[v_axis] = v as i32 - 1;
+                    let voxel_id: BlockId = voxels[Chunk::voxel_index(voxel_pos)];
+                    if open_column >> bit & 1 == 0
+                        && !is_face_visible(
+                            voxel_id,
+                            face_id,
+                            voxel_pos + face.direction(),
+                            neighborhood,
+                            registry,
+                            unloaded,
+                        )
+                    {
+                        continue;
+                    }
+
+                    let ao = face_ao_by(voxel_pos, face, |pos| shading.get(pos));
+                    add_quad(
+                        layer_buffer(voxel_id, registry, &mut opaque, &mut translucent),
+                        registry,
+                        face_id,
+                        voxel_id,
+                        cube_face_origin(voxel_pos, face),
+                        (MODEL_UNITS, MODEL_UNITS),
+                        ao,
+                    );
+                }
+            }
+        }
+    }
+
+    add_block_models(
+        neighborhood,
+        registry,
+        unloaded,
+        &mut opaque,
+        &mut translucent,
+    );
+    (opaque, translucent)
+}
@@ -1,29 +1,138 @@
-use crate::meshes::base_mesh::BaseMesh;
+use crate::blocks::BlockRegistry;
+use crate::meshes::bitmask_mesh_builder::build_bitmask_mesh;
 use crate::meshes::chunk_mesh_builder::build_chunk_mesh;
-use crate::world_objects::Chunk;
+use crate::meshes::greedy_mesh_builder::build_greedy_mesh;
+use crate::meshes::lod_mesh_builder::build_lod_mesh;
+use crate::settings::{MesherKind, UnloadedChunkPolicy};
+use crate::world_objects::ChunkNeighborhood;
 
-pub struct ChunkMesh {
-    base: BaseMesh,
+// Вершина чанка. У буфері — два u32, які розпаковує chunk.vert:
+//   перше: x (10 біт) | y (10) | z (10), 2 біти вільні
+//   друге: voxel_id (16 біт) | face_id (3) | ao (2) | light (4) | texture (7)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkVertex {
+    pub position: [u16; 3], // У частках блока MODEL_UNITS: 0..=CHUNK_SIZE * MODEL_UNITS
+    pub voxel_id: u16,
+    pub face_id: u8,
+    pub ao: u8,       // 0..=3
+    pub light: u8,    // 0..=MAX_LIGHT
+    pub texture: u16, // Шар масиву текстур, 0..MAX_TEXTURES (див. Block::textures)
 }
 
-impl ChunkMesh {
-    pub fn new(chunk: &Chunk) -> Self {
-        let format_size = 5; // 3u1 (позиція) + 1u1 (voxel_id) + 1u1 (face_id)
-        let vertex_data =
-            unsafe { build_chunk_mesh(&chunk.voxels, format_size, chunk.position, &*chunk.world) };
-        let vertex_count = vertex_data.len() as i32 / format_size;
-        let attrs = [(0, 3), (1, 1), (2, 1)]; // Позиція, voxel_id, face_id
+impl ChunkVertex {
+    pub const MAX_LIGHT: u8 = 15;
+
+    pub fn pack(&self) -> [u32; 2] {
+        let [x, y, z] = self.position.map(|c| c as u32 & 0x3FF);
+        let second = self.voxel_id as u32
+            | (self.face_id as u32 & 0x7) << 16
+            | (self.ao as u32 & 0x3) << 19
+            | (self.light as u32 & 0xF) << 21
+            | (self.texture as u32 & 0x7F) << 25;
+        [x | y << 10 | z << 20, second]
+    }
+
+    pub fn unpack([first, second]: [u32; 2]) -> Self {
+        Self {
+            position: [first & 0x3FF, first >> 10 & 0x3FF, first >> 20 & 0x3FF].map(|c| c as u16),
+            voxel_id: (second & 0xFFFF) as u16,
+            face_id: (second >> 16 & 0x7) as u8,
+            ao: (second >> 19 & 0x3) as u8,
+            light: (second >> 21 & 0xF) as u8,
+            texture: (second >> 25 & 0x7F) as u16,
+        }
+    }
+}
+
+// Вершини одного проходу малювання. Кожен квадрат — 4 вершини,
+// трикутники задає спільний індексний буфер (ChunkMesh::quad_indices)
+#[derive(Default)]
+pub struct MeshData {
+    pub vertex_data: Vec<u32>,
+    pub vertex_count: i32,
+}
+
+impl MeshData {
+    // Мешери виділяють буфер із запасом, а меш лежить у черзі до завантаження в GPU
+    fn new(mut vertex_data: Vec<u32>) -> Self {
+        vertex_data.shrink_to_fit();
+        let vertex_count = vertex_data.len() as i32 / ChunkMesh::FORMAT_SIZE;
         Self {
-            base: BaseMesh::new(
-                chunk.shader_program.chunk_program(),
-                &vertex_data,
-                &attrs,
-                vertex_count,
-            ),
+            vertex_data,
+            vertex_count,
         }
     }
 
-    pub fn render(&self) {
-        self.base.render();
+    pub fn is_empty(&self) -> bool {
+        self.vertex_count == 0
+    }
+
+    pub fn quad_count(&self) -> usize {
+        self.vertex_count as usize / 4
+    }
+
+    pub fn index_count(&self) -> i32 {
+        self.quad_count() as i32 * 6
+    }
+
+    pub fn vertices(&self) -> impl Iterator<Item = ChunkVertex> + '_ {
+        self.vertex_data
+            .chunks_exact(ChunkMesh::FORMAT_SIZE as usize)
+            .map(|data| ChunkVertex::unpack([data[0], data[1]]))
+    }
+}
+
+// Вершинні дані чанка на CPU; завантаження в GPU робить рендерер.
+// opaque — непрозорі й cutout-блоки, translucent — напівпрозорі, що малюються
+// окремим проходом після них. Порожній меш (Default) означає, що чанк нічого не малює
+#[derive(Default)]
+pub struct ChunkMesh {
+    pub opaque: MeshData,
+    pub translucent: MeshData,
+}
+
+impl ChunkMesh {
+    pub const FORMAT_SIZE: i32 = 2; // Два u32 на вершину (див. ChunkVertex)
+    pub const ATTRS: [(u32, i32); 1] = [(0, 2)]; // uvec2 з упакованою вершиною
+    pub const QUAD_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+    // Індекси для count квадратів; однакові для всіх чанків
+    pub fn quad_indices(count: usize) -> Vec<u32> {
+        (0..count as u32)
+            .flat_map(|quad| Self::QUAD_INDICES.map(|index| quad * 4 + index))
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.opaque.is_empty() && self.translucent.is_empty()
+    }
+
+    // lod > 0 — меш з вокселів, зменшених у 2^lod разів (див. build_lod_mesh); mesher тоді
+    // не використовується
+    pub fn new(
+        neighborhood: &ChunkNeighborhood,
+        registry: &BlockRegistry,
+        unloaded: UnloadedChunkPolicy,
+        mesher: MesherKind,
+        lod: u8,
+    ) -> Self {
+        let (opaque, translucent) = match mesher {
+            _ if lod > 0 => {
+                build_lod_mesh(neighborhood, registry, unloaded, lod, Self::FORMAT_SIZE)
+            }
+            MesherKind::PerFace => {
+                build_chunk_mesh(neighborhood, registry, unloaded, Self::FORMAT_SIZE)
+            }
+            MesherKind::Greedy => {
+                build_greedy_mesh(neighborhood, registry, unloaded, Self::FORMAT_SIZE)
+            }
+            MesherKind::Bitmask => {
+                build_bitmask_mesh(neighborhood, registry, unloaded, Self::FORMAT_SIZE)
+            }
+        };
+        Self {
+            opaque: MeshData::new(opaque),
+            translucent: MeshData::new(translucent),
+        }
     }
 }
@@ -1,280 +1,356 @@
-use crate::settings::{CHUNK_AREA, CHUNK_SIZE, CHUNK_VOL, MAX_Y, MIN_Y};
-use crate::world::World;
-use crate::world_objects::Chunk;
+use crate::blocks::{AIR, BlockId, BlockModel, BlockRegistry, MODEL_UNITS, ModelBox, RenderLayer};
+use crate::meshes::chunk_mesh::ChunkVertex;
+use crate::settings::{CHUNK_AREA, CHUNK_SIZE, UnloadedChunkPolicy};
+use crate::world_objects::ChunkNeighborhood;
 use glam::IVec3;
 
-// fn is_void(
-//     voxel_pos: (i32, i32, i32),
-//     chunk_voxels: &[u8],
-//     world_voxel_pos: (i32, i32, i32),
-//     world: &World,
-// ) -> bool {
-//     let (x, y, z) = voxel_pos;
-//     if x >= 0
-//         && x < CHUNK_SIZE as i32
-//         && y >= 0
-//         && y < CHUNK_SIZE as i32
-//         && z >= 0
-//         && z < CHUNK_SIZE as i32
-//     {
-//         if chunk_voxels[(x + CHUNK_SIZE as i32 * z + CHUNK_AREA as i32 * y) as usize] != 0 {
-//             return false;
-//         }
-//     }
-//     true
-// }
-
-fn is_void(
-    voxel_pos: (i32, i32, i32),
-    chunk_voxels: &[u8],
-    world_voxel_pos: (i32, i32, i32),
-    world: &World,
-) -> bool {
-    let (x, y, z) = voxel_pos;
-    let (wx, wy, wz) = world_voxel_pos;
+// Грань у площині (a, b) з нормаллю вздовж осі normal. offset — 1, якщо грань
+// лежить на дальньому боці вокселя. reversed — кути (0,0), (1,0), (1,1), (0,1)
+// обходяться у зворотному порядку, щоб грань дивилася назовні
+pub(crate) struct FaceAxes {
+    pub normal: usize,
+    pub a: usize,
+    pub b: usize,
+    pub offset: i32,
+    pub reversed: bool,
+}
 
-    // Якщо воксель у межах поточного чанка
-    if x >= 0
-        && x < CHUNK_SIZE as i32
-        && y >= 0
-        && y < CHUNK_SIZE as i32
-        && z >= 0
-        && z < CHUNK_SIZE as i32
-    {
-        let index = (x + CHUNK_SIZE as i32 * z + CHUNK_AREA as i32 * y) as usize;
-        return chunk_voxels[index] == 0;
+impl FaceAxes {
+    // Зсув до сусіда, якого торкається грань
+    pub fn direction(&self) -> IVec3 {
+        IVec3::AXES[self.normal] * (self.offset * 2 - 1)
     }
+}
 
-    // Якщо воксель поза межами чанка, перевіряємо сусідній чанк
-    let cx = (wx as f32 / CHUNK_SIZE as f32).floor() as i32;
-    let cy = (wy as f32 / CHUNK_SIZE as f32).floor() as i32;
-    let cz = (wz as f32 / CHUNK_SIZE as f32).floor() as i32;
-
-    // let cx = wx.div_euclid(CHUNK_SIZE as i32);
-    // let cy = wy.div_euclid(CHUNK_SIZE as i32);
-    // let cz = wz.div_euclid(CHUNK_SIZE as i32);
+// У порядку face_id: верх, низ, право, ліво, зад, перед
+#[rustfmt::skip]
+pub(crate) const FACES: [FaceAxes; 6] = [
+    FaceAxes { normal: 1, a: 0, b: 2, offset: 1, reversed: true },  // top: a = x, b = z
+    FaceAxes { normal: 1, a: 0, b: 2, offset: 0, reversed: false }, // bottom
+    FaceAxes { normal: 0, a: 1, b: 2, offset: 1, reversed: false }, // right: a = y, b = z
+    FaceAxes { normal: 0, a: 1, b: 2, offset: 0, reversed: true },  // left
+    FaceAxes { normal: 2, a: 0, b: 1, offset: 0, reversed: true },  // back: a = x, b = y
+    FaceAxes { normal: 2, a: 0, b: 1, offset: 1, reversed: false }, // front
+];
 
-    let world_d = world.render_distance * 1;
-    let world_h = MIN_Y.abs() + MAX_Y;
+// Кути грані по (a, b) по колу; у цьому ж порядку йдуть значення AO
+const CORNERS: [(usize, usize); 4] = [(0, 0), (1, 0), (1, 1), (0, 1)];
 
-    if (cx <= world_d
-        || cx > 0
-        || cy <= world_d
-        || cy > 0
-        || cz <= world_d
-        || cz > 0)
-    {
+// Грань face_id блока voxel_id на межі з сусідом на neighbor_pos видно, якщо сусід
+// не закриває спільний бік повністю. Такий непрозорий сусід ховає будь-яку грань,
+// прозорий — лише грані такого самого блока, щоб усередині води чи скла не було стінок
+pub(crate) fn is_face_visible(
+    voxel_id: BlockId,
+    face_id: usize,
+    neighbor_pos: IVec3,
+    neighborhood: &ChunkNeighborhood,
+    registry: &BlockRegistry,
+    unloaded: UnloadedChunkPolicy,
+) -> bool {
+    let Some(neighbor_id) = neighborhood.voxel(neighbor_pos) else {
+        return unloaded == UnloadedChunkPolicy::Air;
+    };
+    // Невідомі блоки вважаємо суцільними кубами
+    let Some(neighbor) = registry.get(neighbor_id) else {
         return false;
+    };
+    // Протилежні грані йдуть парами: верх-низ, право-ліво, зад-перед
+    let covered = neighbor.model.covers(face_id ^ 1);
+    !(covered && (!neighbor.transparent || neighbor_id == voxel_id))
+}
+
+// Вершини напівпрозорих блоків ідуть в окремий буфер
+pub(crate) fn layer_buffer<'a>(
+    voxel_id: BlockId,
+    registry: &BlockRegistry,
+    opaque: &'a mut Vec<u32>,
+    translucent: &'a mut Vec<u32>,
+) -> &'a mut Vec<u32> {
+    match registry.render_layer(voxel_id) {
+        RenderLayer::Translucent => translucent,
+        RenderLayer::Opaque | RenderLayer::Cutout => opaque,
     }
+}
 
-    // if let Some(chunk) = world.chunks.get(&IVec3::new(cx, cy, cz)) {
-    //     let local_x = x.rem_euclid(CHUNK_SIZE as i32);
-    //     let local_y = y.rem_euclid(CHUNK_SIZE as i32);
-    //     let local_z = z.rem_euclid(CHUNK_SIZE as i32);
-    //     let index = (local_x + CHUNK_SIZE as i32 * local_z + CHUNK_AREA as i32 * local_y) as usize;
-    //     return chunk.voxels[index] != 0;
-    // }
+// Затінення кутів грані від 0 (кут закритий з обох боків) до 3 (відкритий) за трьома
+// вокселями перед гранню, що торкаються кута. Сусідні чанки читаються через neighborhood;
+// незавантажені не затіняють
+pub(crate) fn face_ao(
+    voxel_pos: IVec3,
+    face: &FaceAxes,
+    neighborhood: &ChunkNeighborhood,
+    registry: &BlockRegistry,
+) -> [u8; 4] {
+    face_ao_by(voxel_pos, face, |pos| {
+        neighborhood
+            .voxel(pos)
+            .is_some_and(|voxel_id| registry.is_opaque(voxel_id))
+    })
+}
 
-    // Якщо чанк не згенерований і нижче MIN_Y, вважаємо суцільним
-    // if wy < MIN_Y * CHUNK_SIZE as i32{
-    //     return false;
-    // }
+// face_ao з довільною перевіркою, чи затіняє воксель на pos
+pub(crate) fn face_ao_by(
+    voxel_pos: IVec3,
+    face: &FaceAxes,
+    occludes: impl Fn(IVec3) -> bool,
+) -> [u8; 4] {
+    let front = voxel_pos + face.direction();
+    CORNERS.map(|(da, db)| {
+        let side_a = IVec3::AXES[face.a] * (da as i32 * 2 - 1);
+        let side_b = IVec3::AXES[face.b] * (db as i32 * 2 - 1);
+        let (side1, side2) = (occludes(front + side_a), occludes(front + side_b));
+        if side1 && side2 {
+            0
+        } else {
+            3 - side1 as u8 - side2 as u8 - occludes(front + side_a + side_b) as u8
+        }
+    })
+}
 
-    // Інакше вважаємо порожнім
-    true
+// Кут (0, 0) грані face повного куба на voxel_pos, у частках блока
+pub(crate) fn cube_face_origin(voxel_pos: IVec3, face: &FaceAxes) -> IVec3 {
+    (voxel_pos + IVec3::AXES[face.normal] * face.offset) * MODEL_UNITS
 }
 
-fn get_chunk(world_voxel_pos: (i32, i32, i32), world: &World) -> Option<&Chunk> {
-    let (wx, wy, wz) = world_voxel_pos;
-    let cx = (wx as f32 / CHUNK_SIZE as f32).floor() as i32;
-    let cy = (wy as f32 / CHUNK_SIZE as f32).floor() as i32;
-    let cz = (wz as f32 / CHUNK_SIZE as f32).floor() as i32;
-    world.chunks.get(&IVec3::new(cx, cy, cz))
+// Квадрат у площині грані face_id з кутом (0, 0) в origin і розміром size (по a, по b),
+// усе в частках блока. Текстура — та, що задана блоку для цієї грані
+pub(crate) fn add_quad(
+    vertex_data: &mut Vec<u32>,
+    registry: &BlockRegistry,
+    face_id: usize,
+    voxel_id: BlockId,
+    origin: IVec3,
+    size: (i32, i32),
+    ao: [u8; 4],
+) {
+    let face = &FACES[face_id];
+    let corners = CORNERS.map(|(da, db)| {
+        let mut position = origin;
+        position[face.a] += da as i32 * size.0;
+        position[face.b] += db as i32 * size.1;
+        position
+    });
+    let texture = registry.face_texture(voxel_id, face_id);
+    push_quad(vertex_data, face_id, voxel_id, texture, corners, ao, face.reversed);
 }
 
-// fn is_void(
-//     local_voxel_pos: (i32, i32, i32),
-//     world_voxel_pos: (i32, i32, i32),
-//     world: &World,
-// ) -> bool {
-//     let (_, wy, _) = world_voxel_pos;
-//
-//     // Якщо нижче MIN_Y * CHUNK_SIZE, вважаємо заповненим
-//     if wy < MIN_Y * CHUNK_SIZE as i32 {
-//         return false;
-//     }
-//
-//     let chunk = get_chunk(world_voxel_pos, world);
-//     if chunk.is_none() {
-//         return true; // Незгенерований чанк = порожній
-//     }
-//     let chunk_voxels = &chunk.unwrap().voxels;
-//
-//     let (x, y, z) = local_voxel_pos;
-//     let voxel_index = (x.rem_euclid(CHUNK_SIZE as i32)
-//         + z.rem_euclid(CHUNK_SIZE as i32) * CHUNK_SIZE as i32
-//         + y.rem_euclid(CHUNK_SIZE as i32) * CHUNK_AREA as i32) as usize;
-//
-//     if chunk_voxels[voxel_index] != 0 {
-//         return false;
-//     }
-//     true
-// }
+// Чотири кути квадрата по колу, ao — у тому ж порядку. Без reversed квадрат дивиться
+// туди, звідки обхід кутів видно проти годинникової стрілки
+fn push_quad(
+    vertex_data: &mut Vec<u32>,
+    face_id: usize,
+    voxel_id: BlockId,
+    texture: u16,
+    corners: [IVec3; 4],
+    ao: [u8; 4],
+    reversed: bool,
+) {
+    let corner = |index: usize| {
+        ChunkVertex {
+            position: corners[index].to_array().map(|c| c as u16),
+            voxel_id: voxel_id as u16,
+            face_id: face_id as u8,
+            ao: ao[index],
+            light: ChunkVertex::MAX_LIGHT, // Освітлення ще немає
+            texture,
+        }
+        .pack()
+    };
 
-fn add_data(vertex_data: &mut Vec<u8>, vertices: &[(u8, u8, u8, u8, u8)]) {
-    for vertex in vertices {
-        vertex_data.extend_from_slice(&[vertex.0, vertex.1, vertex.2, vertex.3, vertex.4]);
+    // Індекси квадрата фіксовані (ChunkMesh::QUAD_INDICES: 0-1-2, 0-2-3), тож діагональ
+    // і обхід задаються порядком вершин. Діагональ — між світлішою парою кутів, щоб тінь
+    // одного кута не тягнулася через увесь квадрат
+    let first = if ao[0] + ao[2] < ao[1] + ao[3] { 1 } else { 0 };
+    let order = if reversed { [0, 3, 2, 1] } else { [0, 1, 2, 3] };
+    for step in order {
+        vertex_data.extend_from_slice(&corner((first + step) % 4));
     }
 }
 
-pub fn build_chunk_mesh(
-    chunk_voxels: &[u8],
-    format_size: i32,
-    chunk_pos: IVec3,
-    world: &World,
-) -> Vec<u8> {
-    let mut vertex_data = Vec::with_capacity((CHUNK_VOL as usize * 18 * format_size as usize));
-    let mut triangle_count = 0;
+// Геометрія неповної моделі блока. Грані на межі блока відсікаються так само, як у куба;
+// внутрішні малюються завжди, крім прихованих під іншим паралелепіпедом тієї ж моделі.
+// Затінення кутів у неповних моделей немає
+pub(crate) fn add_block_model(
+    vertex_data: &mut Vec<u32>,
+    voxel_id: BlockId,
+    voxel_pos: IVec3,
+    neighborhood: &ChunkNeighborhood,
+    registry: &BlockRegistry,
+    unloaded: UnloadedChunkPolicy,
+) {
+    let model = registry.model(voxel_id);
+    let origin = voxel_pos * MODEL_UNITS;
 
-    for x in 0..CHUNK_SIZE {
-        for y in 0..CHUNK_SIZE {
-            for z in 0..CHUNK_SIZE {
-                let voxel_id = chunk_voxels[(x + CHUNK_SIZE * z + CHUNK_AREA * y) as usize];
-                if voxel_id == 0 {
+    if model == BlockModel::Cross {
+        const CROSS_FACE: usize = 5; // UV — як у передньої грані, вздовж x і y
+        let (min, max) = (origin, origin + MODEL_UNITS);
+        let diagonals = [
+            [
+                min,
+                IVec3::new(max.x, min.y, max.z),
+                max,
+                IVec3::new(min.x, max.y, min.z),
+            ],
+            [
+                IVec3::new(max.x, min.y, min.z),
+                IVec3::new(min.x, min.y, max.z),
+                IVec3::new(min.x, max.y, max.z),
+                IVec3::new(max.x, max.y, min.z),
+            ],
+        ];
+        let texture = registry.face_texture(voxel_id, CROSS_FACE);
+        // Обидва боки кожної площини, бо задні грані відсікаються
+        for corners in diagonals {
+            for reversed in [false, true] {
+                push_quad(vertex_data, CROSS_FACE, voxel_id, texture, corners, [3; 4], reversed);
+            }
+        }
+        return;
+    }
+
+    let boxes = model.boxes();
+    for (box_index, model_box) in boxes.iter().enumerate() {
+        for (face_id, face) in FACES.iter().enumerate() {
+            let plane = if face.offset == 1 {
+                model_box.max[face.normal]
+            } else {
+                model_box.min[face.normal]
+            };
+            let on_boundary = plane == face.offset * MODEL_UNITS;
+            if on_boundary {
+                let neighbor_pos = voxel_pos + face.direction();
+                if !is_face_visible(
+                    voxel_id,
+                    face_id,
+                    neighbor_pos,
+                    neighborhood,
+                    registry,
+                    unloaded,
+                ) {
                     continue;
                 }
+            } else if boxes.iter().enumerate().any(|(other_index, other)| {
+                other_index != box_index && covers_box_face(other, model_box, face, plane)
+            }) {
+                continue;
+            }
 
-                let x = x as u8;
-                let y = y as u8;
-                let z = z as u8;
-
-                let wx = chunk_pos.x * CHUNK_SIZE as i32 + x as i32;
-                let wy = chunk_pos.y * CHUNK_SIZE as i32 + y as i32;
-                let wz = chunk_pos.z * CHUNK_SIZE as i32 + z as i32;
+            let mut quad_origin = origin + IVec3::from_array(model_box.min);
+            quad_origin[face.normal] = origin[face.normal] + plane;
+            let size = (
+                model_box.max[face.a] - model_box.min[face.a],
+                model_box.max[face.b] - model_box.min[face.b],
+            );
+            add_quad(vertex_data, registry, face_id, voxel_id, quad_origin, size, [3; 4]);
+        }
+    }
+}
 
-                // top face ok
-                if is_void(
-                    (x as i32, y as i32 + 1, z as i32),
-                    chunk_voxels,
-                    (wx, wy + 1, wz),
-                    world,
-                ) {
-                    #[rustfmt::skip]
-                    let vertices = [
-                        (x    , y + 1, z    , voxel_id, 0), //v0
-                        (x    , y + 1, z + 1, voxel_id, 0), //v3
-                        (x + 1, y + 1, z + 1, voxel_id, 0), //v2
-                        (x    , y + 1, z    , voxel_id, 0), //v0
-                        (x + 1, y + 1, z + 1, voxel_id, 0), //v2
-                        (x + 1, y + 1, z    , voxel_id, 0), //v1
-                    ];
-                    add_data(&mut vertex_data, &vertices);
-                    triangle_count += 2;
-                }
+// Чи прилягає other до грані face паралелепіпеда model_box (у площині plane) і чи
+// закриває її повністю
+fn covers_box_face(other: &ModelBox, model_box: &ModelBox, face: &FaceAxes, plane: i32) -> bool {
+    let touching = if face.offset == 1 {
+        other.min[face.normal] == plane
+    } else {
+        other.max[face.normal] == plane
+    };
+    touching
+        && [face.a, face.b].iter().all(|&axis| {
+            other.min[axis] <= model_box.min[axis] && other.max[axis] >= model_box.max[axis]
+        })
+}
 
-                // bottom face ok
-                if is_void(
-                    (x as i32, y as i32 - 1, z as i32),
-                    chunk_voxels,
-                    (wx, wy - 1, wz),
-                    world,
-                ) {
-                    #[rustfmt::skip]
-                    let vertices = [
-                        (x    , y, z    , voxel_id, 1), //v0
-                        (x + 1, y, z + 1, voxel_id, 1), //v2
-                        (x    , y, z + 1, voxel_id, 1), //v3
-                        (x    , y, z    , voxel_id, 1), //v0
-                        (x + 1, y, z    , voxel_id, 1), //v1
-                        (x + 1, y, z + 1, voxel_id, 1), //v2
-                    ];
-                    add_data(&mut vertex_data, &vertices);
-                    triangle_count += 2;
+// Усі неповні моделі чанка — для мешерів, які самі будують лише куби.
+// Якщо в палітрі немає моделей, чанк не обходиться
+pub(crate) fn add_block_models(
+    neighborhood: &ChunkNeighborhood,
+    registry: &BlockRegistry,
+    unloaded: UnloadedChunkPolicy,
+    opaque: &mut Vec<u32>,
+    translucent: &mut Vec<u32>,
+) {
+    let chunk = neighborhood.chunk;
+    let has_models = chunk
+        .voxels
+        .palette()
+        .iter()
+        .any(|&voxel_id| voxel_id != AIR && registry.model(voxel_id) != BlockModel::Cube);
+    if !has_models {
+        return;
+    }
+    for x in 0..CHUNK_SIZE {
+        for y in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                let voxel_pos = IVec3::new(x as i32, y as i32, z as i32);
+                let voxel_id = chunk.get_voxel(voxel_pos);
+                if voxel_id == AIR || registry.model(voxel_id) == BlockModel::Cube {
+                    continue;
                 }
+                add_block_model(
+                    layer_buffer(voxel_id, registry, opaque, translucent),
+                    voxel_id,
+                    voxel_pos,
+                    neighborhood,
+                    registry,
+                    unloaded,
+                );
+            }
+        }
+    }
+}
 
-                // right face ok
-                if is_void(
-                    (x as i32 + 1, y as i32, z as i32),
-                    chunk_voxels,
-                    (wx + 1, wy, wz),
-                    world,
-                ) {
-                    #[rustfmt::skip]
-                    let vertices = [
-                        (x + 1, y    , z    , voxel_id, 2), //v0
-                        (x + 1, y + 1, z    , voxel_id, 2), //v1
-                        (x + 1, y + 1, z + 1, voxel_id, 2), //v2
-                        (x + 1, y    , z    , voxel_id, 2), //v0
-                        (x + 1, y + 1, z + 1, voxel_id, 2), //v2
-                        (x + 1, y    , z + 1, voxel_id, 2), //v3
-                    ];
-                    add_data(&mut vertex_data, &vertices);
-                    triangle_count += 2;
-                }
+pub fn build_chunk_mesh(
+    neighborhood: &ChunkNeighborhood,
+    registry: &BlockRegistry,
+    unloaded: UnloadedChunkPolicy,
+    format_size: i32,
+) -> (Vec<u32>, Vec<u32>) {
+    let chunk = neighborhood.chunk;
+    // Під типову поверхню рельєфу: кілька граней на колонку; більше — буфер доросте
+    let mut opaque = Vec::with_capacity(CHUNK_AREA as usize * 4 * 4 * format_size as usize);
+    let mut translucent = Vec::new();
 
-                // left face ok
-                if is_void(
-                    (x as i32 - 1, y as i32, z as i32),
-                    chunk_voxels,
-                    (wx - 1, wy, wz),
-                    world,
-                ) {
-                    #[rustfmt::skip]
-                    let vertices = [
-                        (x, y    , z    , voxel_id, 3), //v0
-                        (x, y + 1, z + 1, voxel_id, 3), //v2
-                        (x, y + 1, z    , voxel_id, 3), //v1
-                        (x, y    , z    , voxel_id, 3), //v0
-                        (x, y    , z + 1, voxel_id, 3), //v3
-                        (x, y + 1, z + 1, voxel_id, 3), //v2
-                    ];
-                    add_data(&mut vertex_data, &vertices);
-                    triangle_count += 2;
+    for x in 0..CHUNK_SIZE {
+        for y in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                let voxel_pos = IVec3::new(x as i32, y as i32, z as i32);
+                let voxel_id = chunk.get_voxel(voxel_pos);
+                if voxel_id == AIR {
+                    continue;
                 }
 
-                // back face ok
-                if is_void(
-                    (x as i32, y as i32, z as i32 - 1),
-                    chunk_voxels,
-                    (wx, wy, wz - 1),
-                    world,
-                ) {
-                    #[rustfmt::skip]
-                    let vertices = [
-                        (x    , y    , z, voxel_id, 4), //v0
-                        (x    , y + 1, z, voxel_id, 4), //v1
-                        (x + 1, y + 1, z, voxel_id, 4), //v2
-                        (x    , y    , z, voxel_id, 4), //v0
-                        (x + 1, y + 1, z, voxel_id, 4), //v2
-                        (x + 1, y    , z, voxel_id, 4), //v3
-                    ];
-                    add_data(&mut vertex_data, &vertices);
-                    triangle_count += 2;
+                let vertex_data = layer_buffer(voxel_id, registry, &mut opaque, &mut translucent);
+                if registry.model(voxel_id) != BlockModel::Cube {
+                    add_block_model(
+                        vertex_data,
+                        voxel_id,
+                        voxel_pos,
+                        neighborhood,
+                        registry,
+                        unloaded,
+                    );
+                    continue;
                 }
 
-                // front face ok
-                if is_void(
-                    (x as i32, y as i32, z as i32 + 1),
-                    chunk_voxels,
-                    (wx, wy, wz + 1),
-                    world,
-                ) {
-                    #[rustfmt::skip]
-                    let vertices = [
-                        (x    , y    , z + 1, voxel_id, 5), //v0
-                        (x + 1, y + 1, z + 1, voxel_id, 5), //v2
-                        (x    , y + 1, z + 1, voxel_id, 5), //v1
-                        (x    , y    , z + 1, voxel_id, 5), //v0
-                        (x + 1, y    , z + 1, voxel_id, 5), //v3
-                        (x + 1, y + 1, z + 1, voxel_id, 5), //v2
-                    ];
-                    add_data(&mut vertex_data, &vertices);
-                    triangle_count += 2;
+                for (face_id, face) in FACES.iter().enumerate() {
+                    let neighbor_pos = voxel_pos + face.direction();
+                    if is_face_visible(
+                        voxel_id,
+                        face_id,
+                        neighbor_pos,
+                        neighborhood,
+                        registry,
+                        unloaded,
+                    ) {
+                        let ao = face_ao(voxel_pos, face, neighborhood, registry);
+                        let origin = cube_face_origin(voxel_pos, face);
+                        let size = (MODEL_UNITS, MODEL_UNITS);
+                        add_quad(vertex_data, registry, face_id, voxel_id, origin, size, ao);
+                    }
                 }
             }
         }
     }
 
-    println!("Chunk at {:?}: {} triangles", chunk_pos, triangle_count);
-    vertex_data
+    (opaque, translucent)
 }
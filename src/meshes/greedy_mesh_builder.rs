@@ -0,0 +1,137 @@
+use crate::blocks::{AIR, BlockModel, BlockRegistry, MODEL_UNITS};
+use crate::meshes::chunk_mesh_builder::{
+    FACES, add_block_models, add_quad, cube_face_origin, face_ao, is_face_visible, layer_buffer,
+};
+use crate::settings::{CHUNK_AREA, CHUNK_SIZE, UnloadedChunkPolicy};
+use crate::world_objects::ChunkNeighborhood;
+use glam::IVec3;
+
+// Видима грань у масці шару: voxel_id у молодшому байті, AO чотирьох кутів по 2 біти
+// над ним. 0 — грані немає (повітря не малюється)
+pub(crate) fn pack_face(voxel_id: u8, ao: [u8; 4]) -> u16 {
+    let ao = ao
+        .iter()
+        .enumerate()
+        .fold(0u16, |packed, (i, &value)| packed | (value as u16) << (i * 2));
+    voxel_id as u16 | ao << 8
+}
+
+fn unpack_face(packed: u16) -> (u8, [u8; 4]) {
+    let ao = std::array::from_fn(|i| ((packed >> (8 + i * 2)) & 0b11) as u8);
+    (packed as u8, ao)
+}
+
+// Зливає грані маски шару size x size (mask[a + size * b]) у прямокутники і віддає кожен
+// в emit(a, b, width, height, voxel_id, ao). Після виклику маска порожня
+pub(crate) fn merge_faces(
+    mask: &mut [u16],
+    size: usize,
+    mut emit: impl FnMut(usize, usize, usize, usize, u8, [u8; 4]),
+) {
+    for b in 0..size {
+        let mut a = 0;
+        while a < size {
+            let packed = mask[a + size * b];
+            if packed == 0 {
+                a += 1;
+                continue;
+            }
+
+            // Зливаємо лише вздовж осі, по якій AO не змінюється, інакше
+            // перехід тіні розтягнувся б на весь квадрат
+            let (voxel_id, ao) = unpack_face(packed);
+            let width = if ao[0] == ao[1] && ao[3] == ao[2] {
+                (a..size)
+                    .take_while(|&i| mask[i + size * b] == packed)
+                    .count()
+            } else {
+                1
+            };
+            let height = if ao[0] == ao[3] && ao[1] == ao[2] {
+                (b..size)
+                    .take_while(|&j| (a..a + width).all(|i| mask[i + size * j] == packed))
+                    .count()
+            } else {
+                1
+            };
+            for j in b..b + height {
+                mask[a + size * j..a + width + size * j].fill(0);
+            }
+
+            emit(a, b, width, height, voxel_id, ao);
+            a += width;
+        }
+    }
+}
+
+// Як build_chunk_mesh, але сусідні видимі грані одного блока з однаковим AO в одній
+// площині зливаються в прямокутники. Формат вершин той самий; текстуру на злитих
+// гранях повторює шейдер. Неповні моделі не зливаються і будуються як у build_chunk_mesh
+pub fn build_greedy_mesh(
+    neighborhood: &ChunkNeighborhood,
+    registry: &BlockRegistry,
+    unloaded: UnloadedChunkPolicy,
+    format_size: i32,
+) -> (Vec<u32>, Vec<u32>) {
+    let chunk = neighborhood.chunk;
+    let size = CHUNK_SIZE as usize;
+    let mut opaque = Vec::with_capacity(CHUNK_AREA as usize * 4 * format_size as usize);
+    let mut translucent = Vec::new();
+    // Видимі грані в поточному шарі: mask[a + CHUNK_SIZE * b]
+    let mut mask = vec![0u16; CHUNK_AREA as usize];
+
+    for (face_id, face) in FACES.iter().enumerate() {
+        for layer in 0..size {
+            for b in 0..size {
+                for a in 0..size {
+                    let mut voxel_pos = IVec3::ZERO;
+                    voxel_pos[face.normal] = layer as i32;
+                    voxel_pos[face.a] = a as i32;
+                    voxel_pos[face.b] = b as i32;
+                    let voxel_id = chunk.get_voxel(voxel_pos);
+                    let neighbor_pos = voxel_pos + face.direction();
+                    mask[a + size * b] = if voxel_id != AIR
+                        && registry.model(voxel_id) == BlockModel::Cube
+                        && is_face_visible(
+                            voxel_id,
+                            face_id,
+                            neighbor_pos,
+                            neighborhood,
+                            registry,
+                            unloaded,
+                        )
+                    {
+                        pack_face(voxel_id, face_ao(voxel_pos, face, neighborhood, registry))
+                    } else {
+                        0
+                    };
+                }
+            }
+
+            merge_faces(&mut mask, size, |a, b, width, height, voxel_id, ao| {
+                let mut voxel_pos = IVec3::ZERO;
+                voxel_pos[face.normal] = layer as i32;
+                voxel_pos[face.a] = a as i32;
+                voxel_pos[face.b] = b as i32;
+                add_quad(
+                    layer_buffer(voxel_id, registry, &mut opaque, &mut translucent),
+                    registry,
+                    face_id,
+                    voxel_id,
+                    cube_face_origin(voxel_pos, face),
+                    (width as i32 * MODEL_UNITS, height as i32 * MODEL_UNITS),
+                    ao,
+                );
+            });
+        }
+    }
+
+    add_block_models(
+        neighborhood,
+        registry,
+        unloaded,
+        &mut opaque,
+        &mut translucent,
+    );
+    (opaque, translucent)
+}
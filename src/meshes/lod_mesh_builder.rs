@@ -0,0 +1,169 @@
+use crate::blocks::{AIR, BlockId, BlockModel, BlockRegistry, MODEL_UNITS};
+use crate::meshes::chunk_mesh_builder::{FACES, add_quad, cube_face_origin, layer_buffer};
+use crate::meshes::greedy_mesh_builder::{merge_faces, pack_face};
+use crate::settings::{CHUNK_SIZE, UnloadedChunkPolicy};
+use crate::world_objects::ChunkNeighborhood;
+use glam::IVec3;
+
+// Клітинки зменшеного чанка разом з шаром клітинок сусідніх чанків навколо.
+// None — клітинка в незавантаженому чанку
+struct CellGrid {
+    cells: usize, // Клітинок уздовж осі всередині чанка
+    data: Vec<Option<BlockId>>,
+}
+
+impl CellGrid {
+    fn index(&self, cell: IVec3) -> usize {
+        let side = self.cells + 2;
+        let cell = (cell + 1).as_uvec3();
+        cell.x as usize + side * (cell.z as usize + side * cell.y as usize)
+    }
+
+    fn get(&self, cell: IVec3) -> Option<BlockId> {
+        self.data[self.index(cell)]
+    }
+
+    fn is_inside(&self, cell: IVec3) -> bool {
+        cell.cmpge(IVec3::ZERO).all() && cell.cmplt(IVec3::splat(self.cells as i32)).all()
+    }
+}
+
+// Клітинка з scale³ вокселів від min: найчастіший кубічний блок, якщо кубічні блоки
+// займають хоча б половину клітинки, інакше повітря. Неповні моделі (рослини, плити)
+// на відстані не видно, тож вони рахуються повітрям
+fn downsample(
+    neighborhood: &ChunkNeighborhood,
+    registry: &BlockRegistry,
+    min: IVec3,
+    scale: i32,
+) -> Option<BlockId> {
+    let mut counts: Vec<(BlockId, u32)> = Vec::new();
+    for y in 0..scale {
+        for z in 0..scale {
+            for x in 0..scale {
+                let voxel_id = neighborhood.voxel(min + IVec3::new(x, y, z))?;
+                if voxel_id == AIR || registry.model(voxel_id) != BlockModel::Cube {
+                    continue;
+                }
+                match counts.iter_mut().find(|(id, _)| *id == voxel_id) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((voxel_id, 1)),
+                }
+            }
+        }
+    }
+    let filled: u32 = counts.iter().map(|(_, count)| count).sum();
+    if filled * 2 < (scale * scale * scale) as u32 {
+        return Some(AIR);
+    }
+    counts
+        .iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(id, _)| *id)
+}
+
+// Грань клітинки видно за тими ж правилами, що й грань блока: її ховає непрозорий
+// сусід або такий самий прозорий
+fn is_cell_face_visible(
+    cell_id: BlockId,
+    neighbor: Option<BlockId>,
+    registry: &BlockRegistry,
+    unloaded: UnloadedChunkPolicy,
+) -> bool {
+    match neighbor {
+        Some(neighbor_id) => !registry.is_opaque(neighbor_id) && neighbor_id != cell_id,
+        None => unloaded == UnloadedChunkPolicy::Air,
+    }
+}
+
+// Меш чанка з вокселів, зменшених у 2^lod разів уздовж кожної осі. Сусідні чанки можуть
+// мати інший рівень деталізації, тож на межі між ними були б щілини. Щоб їх сховати,
+// поверхневі клітинки на краю чанка завжди мають бічні грані — «спідницю» висотою
+// в клітинку, навіть якщо сусідня клітинка їх закриває
+pub fn build_lod_mesh(
+    neighborhood: &ChunkNeighborhood,
+    registry: &BlockRegistry,
+    unloaded: UnloadedChunkPolicy,
+    lod: u8,
+    format_size: i32,
+) -> (Vec<u32>, Vec<u32>) {
+    let scale = 1i32 << lod;
+    let cells = CHUNK_SIZE as i32 / scale;
+    let mut grid = CellGrid {
+        cells: cells as usize,
+        data: vec![Some(AIR); (cells as usize + 2).pow(3)],
+    };
+    // Усередині чанка й шар навколо нього через грані; ребра й кути не потрібні
+    for y in -1..=cells {
+        for z in -1..=cells {
+            for x in -1..=cells {
+                let cell = IVec3::new(x, y, z);
+                let outside = (cell.cmplt(IVec3::ZERO) | cell.cmpge(IVec3::splat(cells)))
+                    .bitmask()
+                    .count_ones();
+                if outside <= 1 {
+                    let index = grid.index(cell);
+                    grid.data[index] = downsample(neighborhood, registry, cell * scale, scale);
+                }
+            }
+        }
+    }
+
+    let mut opaque = Vec::with_capacity(grid.cells.pow(2) * 4 * 4 * format_size as usize);
+    let mut translucent = Vec::new();
+    let size = cells as usize;
+    // Видимі грані клітинок у поточному шарі, як у build_greedy_mesh; AO немає,
+    // тож рівнина зливається в кілька квадратів
+    let mut mask = vec![0u16; size * size];
+
+    for (face_id, face) in FACES.iter().enumerate() {
+        for layer in 0..cells {
+            for b in 0..cells {
+                for a in 0..cells {
+                    let mut cell = IVec3::ZERO;
+                    cell[face.normal] = layer;
+                    cell[face.a] = a;
+                    cell[face.b] = b;
+                    let cell_id = grid.get(cell).unwrap_or(AIR);
+                    let visible = cell_id != AIR && {
+                        let neighbor = cell + face.direction();
+                        let surface = is_cell_face_visible(
+                            cell_id,
+                            grid.get(cell + FACES[0].direction()),
+                            registry,
+                            unloaded,
+                        );
+                        (surface && face.normal != 1 && !grid.is_inside(neighbor))
+                            || is_cell_face_visible(cell_id, grid.get(neighbor), registry, unloaded)
+                    };
+                    mask[a as usize + size * b as usize] = if visible {
+                        pack_face(cell_id, [3; 4])
+                    } else {
+                        0
+                    };
+                }
+            }
+
+            merge_faces(&mut mask, size, |a, b, width, height, cell_id, ao| {
+                let mut cell = IVec3::ZERO;
+                cell[face.normal] = layer;
+                cell[face.a] = a as i32;
+                cell[face.b] = b as i32;
+                add_quad(
+                    layer_buffer(cell_id, registry, &mut opaque, &mut translucent),
+                    registry,
+                    face_id,
+                    cell_id,
+                    cube_face_origin(cell, face) * scale,
+                    (
+                        width as i32 * scale * MODEL_UNITS,
+                        height as i32 * scale * MODEL_UNITS,
+                    ),
+                    ao,
+                );
+            });
+        }
+    }
+
+    (opaque, translucent)
+}
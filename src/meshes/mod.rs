@@ -1,5 +1,5 @@
-pub mod base_mesh;
-pub(crate) mod chunk_mesh;
-mod chunk_mesh_builder;
-mod quad_mesh;
-
+pub mod bitmask_mesh_builder;
+pub mod chunk_mesh;
+pub mod chunk_mesh_builder;
+pub mod greedy_mesh_builder;
+pub mod lod_mesh_builder;
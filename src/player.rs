@@ -1,6 +1,7 @@
-use crate::camera::Camera;
-use crate::settings::Settings;
 use sdl2::EventPump;
+use voxel_engine::camera::Camera;
+use voxel_engine::settings::Settings;
+use voxel_engine::storage::PlayerState;
 
 pub struct Player {
     pub(crate) camera: Camera,
@@ -13,6 +14,22 @@ impl Player {
         }
     }
 
+    // Позиція і напрямок погляду зі збереженого світу
+    pub fn restore(&mut self, state: &PlayerState) {
+        self.camera.position = state.position.into();
+        self.camera.yaw = state.yaw;
+        self.camera.pitch = state.pitch;
+        self.camera.update();
+    }
+
+    pub fn state(&self) -> PlayerState {
+        PlayerState {
+            position: self.camera.position.to_array(),
+            yaw: self.camera.yaw,
+            pitch: self.camera.pitch,
+        }
+    }
+
     pub fn update(&mut self, event_pump: &mut EventPump, delta_time: f32, settings: &Settings) {
         self.keyboard_control(event_pump, delta_time, settings);
         self.mouse_control(event_pump, settings);
@@ -0,0 +1,149 @@
+use std::rc::Rc;
+
+// Буфер індексів (EBO); може бути спільним для багатьох мешів
+pub struct IndexBuffer {
+    ebo: u32,
+}
+
+impl IndexBuffer {
+    pub fn new(indices: &[u32]) -> Self {
+        let mut ebo = 0;
+        unsafe {
+            gl::GenBuffers(1, &mut ebo);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ebo);
+            gl::BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                std::mem::size_of_val(indices) as isize,
+                indices.as_ptr() as *const _,
+                gl::STATIC_DRAW,
+            );
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, 0);
+        }
+        Self { ebo }
+    }
+}
+
+impl Drop for IndexBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteBuffers(1, &self.ebo);
+        }
+    }
+}
+
+pub struct BaseMesh {
+    vao: u32,
+    vbo: u32,
+    vertex_count: i32,
+    // Індексний буфер і кількість індексів; без нього малюється glDrawArrays
+    indices: Option<(Rc<IndexBuffer>, i32)>,
+}
+
+impl BaseMesh {
+    // Цілочисельні атрибути з упакованими в u32 полями; розпаковує шейдер (uint/uvecN)
+    pub fn new_u32(vertex_data: &[u32], attrs: &[(u32, i32)], vertex_count: i32) -> Self {
+        let mut vao = 0;
+        let mut vbo = 0;
+        unsafe {
+            gl::GenVertexArrays(1, &mut vao);
+            gl::BindVertexArray(vao);
+
+            gl::GenBuffers(1, &mut vbo);
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                std::mem::size_of_val(vertex_data) as isize,
+                vertex_data.as_ptr() as *const _,
+                gl::STATIC_DRAW,
+            );
+
+            let mut offset = 0;
+            let stride = attrs.iter().map(|(_, size)| size).sum::<i32>() * std::mem::size_of::<u32>() as i32;
+            for (location, size) in attrs {
+                gl::VertexAttribIPointer(*location, *size, gl::UNSIGNED_INT, stride, offset as *const _);
+                gl::EnableVertexAttribArray(*location);
+                offset += *size as usize * std::mem::size_of::<u32>();
+            }
+
+            gl::BindVertexArray(0);
+        }
+
+        Self { vao, vbo, vertex_count, indices: None }
+    }
+
+    pub fn new_f32(vertex_data: &[f32], attrs: &[(u32, i32)], vertex_count: i32) -> Self {
+        let mut vao = 0;
+        let mut vbo = 0;
+        unsafe {
+            gl::GenVertexArrays(1, &mut vao);
+            gl::BindVertexArray(vao);
+
+            gl::GenBuffers(1, &mut vbo);
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                std::mem::size_of_val(vertex_data) as isize,
+                vertex_data.as_ptr() as *const _,
+                gl::STATIC_DRAW,
+            );
+
+            let mut offset = 0;
+            let stride = attrs.iter().map(|(_, size)| size).sum::<i32>() * std::mem::size_of::<f32>() as i32;
+            for (location, size) in attrs {
+                gl::VertexAttribPointer(*location, *size, gl::FLOAT, gl::FALSE, stride, offset as *const _);
+                gl::EnableVertexAttribArray(*location);
+                offset += *size as usize * std::mem::size_of::<f32>();
+            }
+
+            gl::BindVertexArray(0);
+        }
+
+        Self { vao, vbo, vertex_count, indices: None }
+    }
+
+    // Малювати через індекси; прив'язка EBO зберігається у VAO
+    pub fn with_indices(mut self, indices: Rc<IndexBuffer>, index_count: i32) -> Self {
+        self.set_indices(indices, index_count);
+        self
+    }
+
+    pub fn set_indices(&mut self, indices: Rc<IndexBuffer>, index_count: i32) {
+        unsafe {
+            gl::BindVertexArray(self.vao);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, indices.ebo);
+            gl::BindVertexArray(0);
+        }
+        self.indices = Some((indices, index_count));
+    }
+
+    pub fn vao(&self) -> u32 {
+        self.vao
+    }
+
+    pub fn vbo(&self) -> u32 {
+        self.vbo
+    }
+
+    pub fn render(&self) {
+        unsafe {
+            gl::BindVertexArray(self.vao);
+            match &self.indices {
+                Some((_, index_count)) => {
+                    gl::DrawElements(gl::TRIANGLES, *index_count, gl::UNSIGNED_INT, std::ptr::null())
+                }
+                None => gl::DrawArrays(gl::TRIANGLES, 0, self.vertex_count),
+            }
+        }
+    }
+}
+
+// Меші перестворюються (арена чанків — при рості), тож буфери треба звільняти.
+// Спільний IndexBuffer звільняється, коли його відпустить останній меш
+impl Drop for BaseMesh {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteVertexArrays(1, &self.vao);
+        }
+    }
+}
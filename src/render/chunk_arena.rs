@@ -0,0 +1,270 @@
+use crate::render::base_mesh::{BaseMesh, IndexBuffer};
+use glam::{Vec3, Vec4};
+use std::ops::Range;
+use std::rc::Rc;
+use voxel_engine::meshes::chunk_mesh::{ChunkMesh, MeshData};
+
+// Вершин на сторінку арени. Меш займає цілу кількість сторінок, а шейдер знаходить
+// зсув чанка за номером сторінки вершини, тож дрібні меші далеких LOD не мають
+// марнувати багато місця
+pub const PAGE_VERTICES: usize = 64;
+
+const VERTEX_BYTES: usize = ChunkMesh::FORMAT_SIZE as usize * std::mem::size_of::<u32>();
+const TEXTURE_UNIT: u32 = 1;
+
+// Вільні проміжки сторінок, відсортовані за початком; сусідні проміжки зливаються,
+// щоб звільнене місце знову вміщало великі меші
+struct PageAllocator {
+    pages: usize,
+    free: Vec<Range<usize>>,
+}
+
+impl PageAllocator {
+    fn new(pages: usize) -> Self {
+        let mut allocator = Self {
+            pages: 0,
+            free: Vec::new(),
+        };
+        allocator.grow(pages);
+        allocator
+    }
+
+    // Перший вільний проміжок, у який вміщається count сторінок
+    fn alloc(&mut self, count: usize) -> Option<Range<usize>> {
+        let index = self.free.iter().position(|range| range.len() >= count)?;
+        let range = &mut self.free[index];
+        let pages = range.start..range.start + count;
+        range.start += count;
+        if range.start == range.end {
+            self.free.remove(index);
+        }
+        Some(pages)
+    }
+
+    fn free(&mut self, pages: Range<usize>) {
+        let index = self.free.partition_point(|range| range.start < pages.start);
+        self.free.insert(index, pages);
+        if index + 1 < self.free.len() && self.free[index].end == self.free[index + 1].start {
+            let next = self.free.remove(index + 1);
+            self.free[index].end = next.end;
+        }
+        if index > 0 && self.free[index - 1].end == self.free[index].start {
+            let current = self.free.remove(index);
+            self.free[index - 1].end = current.end;
+        }
+    }
+
+    fn grow(&mut self, pages: usize) {
+        let added = self.pages..pages;
+        self.pages = pages;
+        self.free(added);
+    }
+}
+
+// Місце меша в арені: сторінки й кількість індексів для малювання
+pub struct ArenaRange {
+    pages: Range<usize>,
+    index_count: i32,
+}
+
+// Усі меші чанків в одному VBO з одним VAO. Зсув чанка лежить у буферній текстурі
+// (сторінка -> початок чанка), тож чанки малюються одним glMultiDrawElementsBaseVertex
+// на прохід без перемикання VAO й uniform-ів між ними
+pub struct ChunkArena {
+    mesh: BaseMesh,
+    allocator: PageAllocator,
+    // Буферна текстура вміщує не більше GL_MAX_TEXTURE_BUFFER_SIZE сторінок
+    max_pages: usize,
+    // Про заповнену арену досить сказати один раз
+    full_reported: bool,
+    // Початок чанка для кожної сторінки; копія на CPU, щоб перезавантажити при рості
+    page_origins: Vec<Vec4>,
+    origins_buffer: u32,
+    origins_texture: u32,
+    // Індекси квадратів, спільні для всіх мешів: кожен меш починається з індексу 0,
+    // а base vertex зсуває їх на його сторінки. Ростуть під найбільший меш
+    quad_indices: Rc<IndexBuffer>,
+    quad_capacity: usize,
+}
+
+impl ChunkArena {
+    pub fn new(pages: usize, quad_capacity: usize) -> Self {
+        let quad_indices = Rc::new(IndexBuffer::new(&ChunkMesh::quad_indices(quad_capacity)));
+        let mut max_texels = 0;
+        let mut origins_buffer = 0;
+        let mut origins_texture = 0;
+        unsafe {
+            gl::GetIntegerv(gl::MAX_TEXTURE_BUFFER_SIZE, &mut max_texels);
+            gl::GenBuffers(1, &mut origins_buffer);
+            gl::GenTextures(1, &mut origins_texture);
+        }
+        let max_pages = max_texels as usize;
+        let pages = pages.min(max_pages);
+        let arena = Self {
+            mesh: Self::create_mesh(pages, &quad_indices),
+            allocator: PageAllocator::new(pages),
+            max_pages,
+            full_reported: false,
+            page_origins: vec![Vec4::ZERO; pages],
+            origins_buffer,
+            origins_texture,
+            quad_indices,
+            quad_capacity,
+        };
+        arena.upload_origins();
+        arena
+    }
+
+    // Порожній VBO на pages сторінок; VAO одразу пам'ятає і спільні індекси
+    fn create_mesh(pages: usize, quad_indices: &Rc<IndexBuffer>) -> BaseMesh {
+        let vertex_data = vec![0; pages * PAGE_VERTICES * ChunkMesh::FORMAT_SIZE as usize];
+        BaseMesh::new_u32(&vertex_data, &ChunkMesh::ATTRS, 0).with_indices(quad_indices.clone(), 0)
+    }
+
+    fn upload_origins(&self) {
+        unsafe {
+            gl::BindBuffer(gl::TEXTURE_BUFFER, self.origins_buffer);
+            gl::BufferData(
+                gl::TEXTURE_BUFFER,
+                std::mem::size_of_val(self.page_origins.as_slice()) as isize,
+                self.page_origins.as_ptr() as *const _,
+                gl::DYNAMIC_DRAW,
+            );
+            gl::BindTexture(gl::TEXTURE_BUFFER, self.origins_texture);
+            gl::TexBuffer(gl::TEXTURE_BUFFER, gl::RGBA32F, self.origins_buffer);
+            gl::BindTexture(gl::TEXTURE_BUFFER, 0);
+        }
+    }
+
+    // Удвічі більший VBO; уже завантажені меші копіюються на GPU і лишаються на своїх сторінках.
+    // false — арена вже на межі буферної текстури і місця для min_pages не буде
+    fn grow(&mut self, min_pages: usize) -> bool {
+        let old_pages = self.allocator.pages;
+        if old_pages + min_pages > self.max_pages {
+            return false;
+        }
+        let pages = (old_pages * 2)
+            .max(old_pages + min_pages)
+            .min(self.max_pages);
+        let mesh = Self::create_mesh(pages, &self.quad_indices);
+        unsafe {
+            gl::BindBuffer(gl::COPY_READ_BUFFER, self.mesh.vbo());
+            gl::BindBuffer(gl::COPY_WRITE_BUFFER, mesh.vbo());
+            gl::CopyBufferSubData(
+                gl::COPY_READ_BUFFER,
+                gl::COPY_WRITE_BUFFER,
+                0,
+                0,
+                (old_pages * PAGE_VERTICES * VERTEX_BYTES) as isize,
+            );
+        }
+        self.mesh = mesh;
+        self.allocator.grow(pages);
+        self.page_origins.resize(pages, Vec4::ZERO);
+        self.upload_origins();
+        true
+    }
+
+    pub fn upload(&mut self, mesh: &MeshData, origin: Vec3) -> Option<ArenaRange> {
+        if mesh.is_empty() {
+            return None;
+        }
+        if mesh.quad_count() > self.quad_capacity {
+            self.quad_capacity = mesh.quad_count().next_power_of_two();
+            self.quad_indices = Rc::new(IndexBuffer::new(&ChunkMesh::quad_indices(
+                self.quad_capacity,
+            )));
+            self.mesh.set_indices(self.quad_indices.clone(), 0);
+        }
+
+        let count = (mesh.vertex_count as usize).div_ceil(PAGE_VERTICES);
+        let pages = match self.allocator.alloc(count) {
+            Some(pages) => pages,
+            None if self.grow(count) => self
+                .allocator
+                .alloc(count)
+                .expect("arena has grown to fit the mesh"),
+            None => {
+                if !self.full_reported {
+                    eprintln!(
+                        "Chunk arena is full ({} pages, GL_MAX_TEXTURE_BUFFER_SIZE); some chunks are not drawn",
+                        self.allocator.pages
+                    );
+                    self.full_reported = true;
+                }
+                return None;
+            }
+        };
+
+        let page_origin = origin.extend(0.0);
+        self.page_origins[pages.clone()].fill(page_origin);
+        unsafe {
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.mesh.vbo());
+            gl::BufferSubData(
+                gl::ARRAY_BUFFER,
+                (pages.start * PAGE_VERTICES * VERTEX_BYTES) as isize,
+                std::mem::size_of_val(mesh.vertex_data.as_slice()) as isize,
+                mesh.vertex_data.as_ptr() as *const _,
+            );
+            gl::BindBuffer(gl::TEXTURE_BUFFER, self.origins_buffer);
+            gl::BufferSubData(
+                gl::TEXTURE_BUFFER,
+                (pages.start * std::mem::size_of::<Vec4>()) as isize,
+                (pages.len() * std::mem::size_of::<Vec4>()) as isize,
+                self.page_origins[pages.start..].as_ptr() as *const _,
+            );
+        }
+        Some(ArenaRange {
+            pages,
+            index_count: mesh.index_count(),
+        })
+    }
+
+    pub fn free(&mut self, range: ArenaRange) {
+        self.allocator.free(range.pages);
+    }
+
+    // Один виклик малювання на всі діапазони в переданому порядку. Саме
+    // glMultiDrawElementsBaseVertex, а не glMultiDrawArrays: квадрат — 4 вершини і спільні
+    // 6 індексів, тоді як без індексів кожен квадрат займав би 6 вершин, у півтора раза
+    // більше пам'яті арени. Base vertex дає всім мешам той самий буфер індексів від нуля
+    pub fn draw<'a>(&self, ranges: impl Iterator<Item = &'a ArenaRange>) {
+        let mut counts = Vec::new();
+        let mut base_vertices = Vec::new();
+        for range in ranges {
+            counts.push(range.index_count);
+            base_vertices.push((range.pages.start * PAGE_VERTICES) as i32);
+        }
+        if counts.is_empty() {
+            return;
+        }
+        let offsets = vec![std::ptr::null::<std::ffi::c_void>(); counts.len()];
+        unsafe {
+            gl::ActiveTexture(gl::TEXTURE0 + TEXTURE_UNIT);
+            gl::BindTexture(gl::TEXTURE_BUFFER, self.origins_texture);
+            gl::BindVertexArray(self.mesh.vao());
+            gl::MultiDrawElementsBaseVertex(
+                gl::TRIANGLES,
+                counts.as_ptr(),
+                gl::UNSIGNED_INT,
+                offsets.as_ptr(),
+                counts.len() as i32,
+                base_vertices.as_ptr(),
+            );
+            gl::ActiveTexture(gl::TEXTURE0);
+        }
+    }
+
+    pub fn texture_unit() -> i32 {
+        TEXTURE_UNIT as i32
+    }
+}
+
+impl Drop for ChunkArena {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteTextures(1, &self.origins_texture);
+            gl::DeleteBuffers(1, &self.origins_buffer);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod base_mesh;
+pub mod chunk_arena;
+#[allow(dead_code)]
+pub mod quad_mesh;
+pub mod world_renderer;
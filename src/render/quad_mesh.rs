@@ -1,43 +1,42 @@
-use crate::meshes::base_mesh::BaseMesh;
-use crate::shader_program::ShaderProgram;
+use crate::render::base_mesh::{BaseMesh, IndexBuffer};
+use std::rc::Rc;
 
 pub struct QuadMesh {
     base: BaseMesh,
 }
 
 impl QuadMesh {
-    pub fn new(shader_program: &ShaderProgram) -> Self {
+    pub fn new() -> Self {
         // Розділені позиції (аналог vertices у Python)
-        let positions: [f32; 18] = [
+        let positions: [f32; 12] = [
             0.5, 0.5, 0.0,   // Вершина 1
             -0.5, 0.5, 0.0,  // Вершина 2
             -0.5, -0.5, 0.0, // Вершина 3
-            0.5, 0.5, 0.0,   // Вершина 4
-            -0.5, -0.5, 0.0, // Вершина 5
-            0.5, -0.5, 0.0,  // Вершина 6
+            0.5, -0.5, 0.0,  // Вершина 4
         ];
 
         // Розділені кольори (аналог colors у Python)
-        let colors: [f32; 18] = [
+        let colors: [f32; 12] = [
             0.0, 1.0, 0.0, // Зелений
             1.0, 0.0, 0.0, // Червоний
             1.0, 1.0, 0.0, // Жовтий
-            0.0, 1.0, 0.0, // Зелений
-            1.0, 1.0, 0.0, // Жовтий
             0.0, 0.0, 1.0, // Синій
         ];
 
+        // Два трикутники зі спільними вершинами 1 і 3
+        let indices: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
         // Об'єднання позицій і кольорів у єдиний масив (аналог np.hstack)
-        let mut vertex_data = Vec::with_capacity(36);
-        for i in 0..6 {
+        let mut vertex_data = Vec::with_capacity(24);
+        for i in 0..4 {
             vertex_data.extend_from_slice(&positions[i * 3..i * 3 + 3]); // Додаємо позицію
             vertex_data.extend_from_slice(&colors[i * 3..i * 3 + 3]);    // Додаємо колір
         }
 
         let attrs = [(0, 3), (1, 3)]; // Позиція (3f) + колір (3f)
         Self {
-            base: BaseMesh::new_f32(shader_program.chunk_program(), &vertex_data, &attrs, 6),
-            // base: BaseMesh::new(shader_program.chunk_program(), &vertex_data, &attrs, 6),
+            base: BaseMesh::new_f32(&vertex_data, &attrs, 4)
+                .with_indices(Rc::new(IndexBuffer::new(&indices)), 6),
         }
     }
 
@@ -0,0 +1,107 @@
+use crate::render::chunk_arena::{ArenaRange, ChunkArena, PAGE_VERTICES};
+use crate::shader_program::ShaderProgram;
+use glam::{IVec3, Vec3};
+use std::collections::HashMap;
+use voxel_engine::settings::{CHUNK_SIZE, CHUNK_VOL, Settings};
+use voxel_engine::world::World;
+
+struct ChunkRenderMesh {
+    opaque: Option<ArenaRange>,
+    translucent: Option<ArenaRange>,
+    center: Vec3,
+}
+
+// Тримає меші чанків в арені й синхронізує їх зі станом World
+pub struct WorldRenderer {
+    shader_program: ShaderProgram,
+    meshes: HashMap<IVec3, ChunkRenderMesh>,
+    arena: ChunkArena,
+    uploads_per_frame: usize,
+}
+
+// Стільки квадратів дає чанк, у якому кожен другий блок — куб з усіма відкритими гранями
+const INITIAL_QUAD_CAPACITY: usize = CHUNK_VOL as usize / 2 * 6;
+// Початковий розмір арени: 8 МБ вершин, далі вона росте вдвічі
+const INITIAL_ARENA_PAGES: usize = (1 << 20) / PAGE_VERTICES;
+
+impl WorldRenderer {
+    pub fn new(shader_program: &ShaderProgram, settings: &Settings) -> Self {
+        Self {
+            shader_program: shader_program.clone(),
+            meshes: HashMap::new(),
+            arena: ChunkArena::new(INITIAL_ARENA_PAGES, INITIAL_QUAD_CAPACITY),
+            uploads_per_frame: settings.mesh_uploads_per_frame,
+        }
+    }
+
+    pub fn update(&mut self, world: &mut World) {
+        // Вивантажені чанки більше не малюємо, їхні сторінки арени звільняються
+        let unloaded: Vec<IVec3> = self
+            .meshes
+            .keys()
+            .filter(|pos| world.chunk(**pos).is_none())
+            .copied()
+            .collect();
+        for chunk_pos in unloaded {
+            self.remove(chunk_pos);
+        }
+
+        // Решта готових мешів лишається в чанках до наступних кадрів
+        for (chunk_pos, chunk_mesh) in world.take_meshes(self.uploads_per_frame) {
+            self.remove(chunk_pos);
+            // Порожні й закопані чанки не займають місця в арені
+            if chunk_mesh.is_empty() {
+                continue;
+            }
+            let origin = chunk_pos.as_vec3() * CHUNK_SIZE as f32;
+            let render_mesh = ChunkRenderMesh {
+                opaque: self.arena.upload(&chunk_mesh.opaque, origin),
+                translucent: self.arena.upload(&chunk_mesh.translucent, origin),
+                center: origin + Vec3::splat(CHUNK_SIZE as f32 / 2.0),
+            };
+            self.meshes.insert(chunk_pos, render_mesh);
+        }
+    }
+
+    fn remove(&mut self, chunk_pos: IVec3) {
+        if let Some(mesh) = self.meshes.remove(&chunk_pos) {
+            for range in [mesh.opaque, mesh.translucent].into_iter().flatten() {
+                self.arena.free(range);
+            }
+        }
+    }
+
+    // Спершу непрозорі чанки без змішування, потім напівпрозорі від дальніх до ближніх
+    // до камери: змішування із записом глибини вимкнено, тож порядок визначає результат.
+    // Кожен прохід — один виклик малювання, який зберігає порядок чанків
+    pub fn render(&self, camera_pos: Vec3) {
+        unsafe {
+            gl::UseProgram(self.shader_program.chunk_program());
+            gl::Disable(gl::BLEND);
+        }
+        self.arena.draw(
+            self.meshes
+                .values()
+                .filter_map(|chunk| chunk.opaque.as_ref()),
+        );
+
+        let mut translucent: Vec<_> = self
+            .meshes
+            .values()
+            .filter_map(|chunk| Some((chunk.center, chunk.translucent.as_ref()?)))
+            .collect();
+        translucent.sort_by(|(a, _), (b, _)| {
+            b.distance_squared(camera_pos)
+                .total_cmp(&a.distance_squared(camera_pos))
+        });
+        unsafe {
+            gl::Enable(gl::BLEND);
+            gl::DepthMask(gl::FALSE);
+        }
+        self.arena
+            .draw(translucent.into_iter().map(|(_, range)| range));
+        unsafe {
+            gl::DepthMask(gl::TRUE);
+        }
+    }
+}
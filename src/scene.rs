@@ -1,24 +1,53 @@
-use crate::shader_program::ShaderProgram;
-use crate::world::World;
-use crate::settings::Settings;
 use crate::player::Player;
+use crate::render::world_renderer::WorldRenderer;
+use crate::shader_program::ShaderProgram;
+use std::sync::Arc;
+use voxel_engine::blocks::BlockRegistry;
+use voxel_engine::settings::Settings;
+use voxel_engine::world::{StreamingStats, World};
 
 pub struct Scene {
     world: World,
+    world_renderer: WorldRenderer,
 }
 
 impl Scene {
-    pub fn new(shader_program: &ShaderProgram, settings: &Settings) -> Self {
+    pub fn new(
+        shader_program: &ShaderProgram,
+        settings: &Settings,
+        registry: Arc<BlockRegistry>,
+    ) -> Self {
+        let mut world = World::new(settings, registry);
+        let mut world_renderer = WorldRenderer::new(shader_program, settings);
+        world_renderer.update(&mut world);
         Self {
-            world: World::new(shader_program, settings),
+            world,
+            world_renderer,
         }
     }
 
     pub fn update(&mut self, player: &Player) {
-        self.world.update(player);
+        self.world.update(player.camera.position, player.camera.forward);
+        self.world_renderer.update(&mut self.world);
+    }
+
+    pub fn stats(&self) -> StreamingStats {
+        self.world.stats()
+    }
+
+    // Віддає змінені чанки на запис у фоні
+    pub fn save(&mut self) {
+        self.world.save_modified();
     }
 
-    pub fn render(&self) {
-        self.world.render();
+    // Дочікується запису всіх чанків; при виході
+    pub fn wait_for_saves(&mut self) {
+        if let Err(err) = self.world.wait_for_saves() {
+            eprintln!("Failed to save world: {}", err);
+        }
     }
-}
\ No newline at end of file
+
+    pub fn render(&self, player: &Player) {
+        self.world_renderer.render(player.camera.position);
+    }
+}
@@ -1,13 +1,75 @@
 use glam::{Vec2, Vec3};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
 
 pub const CHUNK_SIZE: u32 = 32;
 pub const H_CHUNK_SIZE: u32 = CHUNK_SIZE / 2;
 pub const CHUNK_AREA: u32 = CHUNK_SIZE * CHUNK_SIZE;
 pub const CHUNK_VOL: u32 = CHUNK_AREA * CHUNK_SIZE;
-pub const MIN_Y: i32 = -2; // Нижня межа світу в чанках
-pub const MAX_Y: i32 = 2; // Верхня межа світу
+pub const MIN_Y: i32 = -2; // Нижня межа світу в чанках за замовчуванням
+pub const MAX_Y: i32 = 2; // Верхня межа світу за замовчуванням
 // pub const WORLD_D: i32 =
 
+// Як мешер трактує воксели в сусідніх чанках, які ще не завантажені
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnloadedChunkPolicy {
+    Solid, // Межа світу закрита, грані на краю не малюються
+    Air,   // Грані на краю малюються, як ніби поруч порожнеча
+}
+
+// Вертикальні межі світу в чанках
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorldHeight {
+    Bounded { min_y: i32, max_y: i32 }, // Включно з обох боків
+    Cubic, // Без меж: по Y чанки підвантажуються навколо гравця, як по X і Z
+}
+
+impl WorldHeight {
+    pub fn contains(&self, chunk_y: i32) -> bool {
+        match *self {
+            WorldHeight::Bounded { min_y, max_y } => (min_y..=max_y).contains(&chunk_y),
+            WorldHeight::Cubic => true,
+        }
+    }
+
+    // Чанки по Y, які треба тримати завантаженими навколо center_y
+    pub fn visible_range(&self, center_y: i32, render_distance: i32) -> RangeInclusive<i32> {
+        let (low, high) = (center_y - render_distance, center_y + render_distance);
+        match *self {
+            WorldHeight::Bounded { min_y, max_y } => low.max(min_y)..=high.min(max_y),
+            WorldHeight::Cubic => low..=high,
+        }
+    }
+
+    // Межі світу в блоках: [bottom, top). None для кубічних чанків
+    pub fn block_range(&self) -> Option<(i32, i32)> {
+        match *self {
+            WorldHeight::Bounded { min_y, max_y } => {
+                Some((min_y * CHUNK_SIZE as i32, (max_y + 1) * CHUNK_SIZE as i32))
+            }
+            WorldHeight::Cubic => None,
+        }
+    }
+}
+
+// Як будуються меші чанків (див. meshes::chunk_mesh::ChunkMesh::new)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MesherKind {
+    PerFace, // Окремий квадрат на кожну видиму грань
+    Greedy,  // Сусідні грані одного блока зливаються
+    Bitmask, // Ті самі грані, що й PerFace, але видимість шукається побітовими масками
+}
+
+// Який WorldGenerator створить World (див. generators::create_generator)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GeneratorKind {
+    Noise,
+    Flat { layers: Vec<(String, u32)> }, // (ім'я блока, товщина) знизу вгору від y = 0
+    Void,
+    Debug,
+}
+
 #[derive(Debug)]
 pub struct Settings {
     pub win_res: Vec2,
@@ -28,11 +90,35 @@ pub struct Settings {
     pub mouse_sensitivity: f32,
     pub bg_color: Vec3,
     pub render_distance: i32, // Додано
+    // Відстані в чанках від гравця, з яких меші будуються з вокселів, зменшених у 2, 4 і 8 разів
+    pub lod_distances: [i32; 3],
     pub world_seed: u32,      // Додано
+    pub world_height: WorldHeight,
+    pub world_dir: Option<PathBuf>, // Каталог світу (див. storage); None — світ не зберігається
+    pub autosave_interval: f32,     // Секунди між автозбереженнями
+    pub unloaded_chunks: UnloadedChunkPolicy,
+    pub generator: GeneratorKind,
+    pub generation_threads: usize,
+    pub meshing_threads: usize,
+    pub mesher: MesherKind,
+    // Бюджети на кадр: скільки чанків віддати на генерацію, на мешинг і завантажити в GPU
+    pub chunks_generated_per_frame: usize,
+    pub chunks_meshed_per_frame: usize,
+    pub mesh_uploads_per_frame: usize,
     // pub world_d: u8,
     // pub world_h: u8,
 }
 
+fn worker_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |threads| threads.get().saturating_sub(1))
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Settings {
     pub fn new() -> Self {
         let win_res = Vec2::new(1600.0, 900.0);
@@ -59,8 +145,26 @@ impl Settings {
             player_pos: Vec3::new(H_CHUNK_SIZE as f32 + 15.0, CHUNK_SIZE as f32, 1.5 * CHUNK_SIZE as f32),
             mouse_sensitivity: 0.002,
             bg_color: Vec3::new(0.1, 0.16, 0.25),
-            render_distance: 3, // Значення за замовчуванням
+            render_distance: 32, // Далекі чанки малюються спрощеними, див. lod_distances
+            lod_distances: [4, 8, 16],
             world_seed: 0,      // Значення за замовчуванням
+            world_height: WorldHeight::Bounded {
+                min_y: MIN_Y,
+                max_y: MAX_Y,
+            },
+            world_dir: None,
+            autosave_interval: 60.0,
+            unloaded_chunks: UnloadedChunkPolicy::Solid,
+            generator: GeneratorKind::Noise,
+            // Один потік лишаємо для рендерингу, решту ділимо між генерацією і мешингом
+            generation_threads: (worker_threads() / 2).max(1),
+            meshing_threads: (worker_threads() / 2).max(1),
+            // Greedy на рельєфі з AO зливає мало граней (на 20-45% менше квадратів), а будується
+            // в кілька разів довше за Bitmask, і це затримує появу чанків при стрімінгу
+            mesher: MesherKind::Bitmask,
+            chunks_generated_per_frame: 8,
+            chunks_meshed_per_frame: 8,
+            mesh_uploads_per_frame: 8,
         }
     }
 
@@ -1,13 +1,22 @@
 use crate::player::Player;
-use glam::Mat4;
+use crate::render::chunk_arena::ChunkArena;
+use glam::{Mat4, Vec4};
+use voxel_engine::blocks::BlockRegistry;
+
+// Точка прив'язки uniform-буфера з кольорами блоків
+const BLOCK_COLORS_BINDING: u32 = 0;
 
 #[derive(Clone)]
 pub struct ShaderProgram {
     chunk: u32,
+    // Кольори блоків (індекс = id, в альфі — opacity) для блоку BlockColors у chunk.vert.
+    // Масив у звичайних uniform-ах не вміщується в гарантований GL 3.3 ліміт
+    // вершинного шейдера
+    block_colors: u32,
 }
 
 impl ShaderProgram {
-    pub fn new(player: &Player) -> Self {
+    pub fn new(player: &Player, registry: &BlockRegistry) -> Self {
         let chunk = unsafe {
             let vertex_shader =
                 compile_shader(include_str!("shaders/chunk.vert"), gl::VERTEX_SHADER);
@@ -22,16 +31,21 @@ impl ShaderProgram {
             program
         };
 
-        let mut this = Self { chunk };
-        this.set_uniforms_on_init(player);
+        let mut this = Self {
+            chunk,
+            block_colors: 0,
+        };
+        this.set_uniforms_on_init(player, registry);
         this
     }
 
-    fn set_uniforms_on_init(&mut self, player: &Player) {
+    fn set_uniforms_on_init(&mut self, player: &Player, registry: &BlockRegistry) {
         unsafe {
             gl::UseProgram(self.chunk);
             set_uniform_mat4(self.chunk, "m_proj", player.m_proj());
-            set_uniform_mat4(self.chunk, "m_model", &Mat4::IDENTITY);
+            set_uniform_int(self.chunk, "u_page_origins", ChunkArena::texture_unit());
+            self.block_colors = create_uniform_buffer(&registry.colors());
+            bind_uniform_block(self.chunk, "BlockColors", BLOCK_COLORS_BINDING, self.block_colors);
         }
     }
 
@@ -46,36 +60,71 @@ impl ShaderProgram {
 }
 
 unsafe fn compile_shader(source: &str, shader_type: u32) -> u32 {
-    let shader = gl::CreateShader(shader_type);
-    gl::ShaderSource(
-        shader,
-        1,
-        &(source.as_ptr() as *const _),
-        &(source.len() as i32),
-    );
-    gl::CompileShader(shader);
-    let mut success = 0;
-    gl::GetShaderiv(shader, gl::COMPILE_STATUS, &mut success);
-    if success == gl::FALSE as i32 {
-        let mut len = 0;
-        gl::GetShaderiv(shader, gl::INFO_LOG_LENGTH, &mut len);
-        let mut buffer = Vec::with_capacity(len as usize);
-        buffer.set_len((len as usize) - 1);
-        gl::GetShaderInfoLog(
+    unsafe {
+        let shader = gl::CreateShader(shader_type);
+        gl::ShaderSource(
             shader,
-            len,
-            std::ptr::null_mut(),
-            buffer.as_mut_ptr() as *mut _,
-        );
-        panic!(
-            "Shader compilation error: {}",
-            String::from_utf8_lossy(&buffer)
+            1,
+            &(source.as_ptr() as *const _),
+            &(source.len() as i32),
         );
+        gl::CompileShader(shader);
+        let mut success = 0;
+        gl::GetShaderiv(shader, gl::COMPILE_STATUS, &mut success);
+        if success == gl::FALSE as i32 {
+            let mut len = 0;
+            gl::GetShaderiv(shader, gl::INFO_LOG_LENGTH, &mut len);
+            let mut buffer = vec![0u8; len as usize];
+            gl::GetShaderInfoLog(
+                shader,
+                len,
+                std::ptr::null_mut(),
+                buffer.as_mut_ptr() as *mut _,
+            );
+            buffer.pop(); // Нуль-термінатор
+            panic!(
+                "Shader compilation error: {}",
+                String::from_utf8_lossy(&buffer)
+            );
+        }
+        shader
     }
-    shader
 }
 
 pub unsafe fn set_uniform_mat4(program: u32, name: &str, matrix: &Mat4) {
-    let loc = gl::GetUniformLocation(program, format!("{}\0", name).as_ptr() as *const _);
-    gl::UniformMatrix4fv(loc, 1, gl::FALSE, matrix.as_ref().as_ptr());
+    unsafe {
+        let loc = gl::GetUniformLocation(program, format!("{}\0", name).as_ptr() as *const _);
+        gl::UniformMatrix4fv(loc, 1, gl::FALSE, matrix.as_ref().as_ptr());
+    }
+}
+
+pub unsafe fn set_uniform_int(program: u32, name: &str, value: i32) {
+    unsafe {
+        let loc = gl::GetUniformLocation(program, format!("{}\0", name).as_ptr() as *const _);
+        gl::Uniform1i(loc, value);
+    }
+}
+
+unsafe fn create_uniform_buffer(values: &[Vec4]) -> u32 {
+    unsafe {
+        let mut buffer = 0;
+        gl::GenBuffers(1, &mut buffer);
+        gl::BindBuffer(gl::UNIFORM_BUFFER, buffer);
+        gl::BufferData(
+            gl::UNIFORM_BUFFER,
+            std::mem::size_of_val(values) as isize,
+            values.as_ptr() as *const _,
+            gl::STATIC_DRAW,
+        );
+        gl::BindBuffer(gl::UNIFORM_BUFFER, 0);
+        buffer
+    }
+}
+
+unsafe fn bind_uniform_block(program: u32, name: &str, binding: u32, buffer: u32) {
+    unsafe {
+        let index = gl::GetUniformBlockIndex(program, format!("{}\0", name).as_ptr() as *const _);
+        gl::UniformBlockBinding(program, index, binding);
+        gl::BindBufferBase(gl::UNIFORM_BUFFER, binding, buffer);
+    }
 }
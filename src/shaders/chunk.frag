@@ -5,17 +5,26 @@ layout (location = 0) out vec4 fragColor;
 const vec3 gamma = vec3(2.2);
 const vec3 inv_gamma = 1 / gamma;
 
-uniform sampler2D u_texture_0;
+uniform sampler2DArray u_textures; // Шар = індекс у BlockRegistry::texture_names
 
-in vec3 voxel_color;
+in vec4 voxel_color;
 in vec2 uv;
+in float ao;
+in float light;
+flat in int texture_layer;
 
 void main() {
-    vec3 tex_col = texture(u_texture_0, uv).rgb;
-    tex_col = pow(tex_col, gamma);
+    vec4 tex = texture(u_textures, vec3(uv, texture_layer));
+    // Alpha test для cutout-блоків (листя); непрозорі текстури його не помічають
+    if (tex.a < 0.5) {
+        discard;
+    }
+    vec3 tex_col = pow(tex.rgb, gamma);
 
-    tex_col.rgb *= voxel_color;
+    tex_col.rgb *= voxel_color.rgb;
+    tex_col.rgb *= mix(0.35, 1.0, ao); // Затінення кутів
+    tex_col.rgb *= mix(0.1, 1.0, light);
 
     tex_col = pow(tex_col, inv_gamma);
-    fragColor = vec4(tex_col, 1);
+    fragColor = vec4(tex_col, voxel_color.a);
 }
\ No newline at end of file
@@ -1,35 +1,56 @@
 #version 330 core
 
-layout (location = 0) in ivec3 in_position;
-layout (location = 1) in int voxel_id;
-layout (location = 2) in int face_id;
+// Упакована вершина (див. ChunkVertex у chunk_mesh.rs)
+layout (location = 0) in uvec2 packed_vertex;
 
 uniform mat4 m_proj;
 uniform mat4 m_view;
-uniform mat4 m_model;
+// Початок чанка для кожної сторінки арени (див. ChunkArena у chunk_arena.rs)
+uniform samplerBuffer u_page_origins;
+// З BlockRegistry, індекс = voxel_id; в альфі — opacity
+layout (std140) uniform BlockColors {
+    vec4 block_colors[256];
+};
 
-out vec3 voxel_color;
+out vec4 voxel_color;
 out vec2 uv;
+out float ao;
+out float light;
+flat out int texture_layer;
 
-const vec2 uv_coords[4] = vec2[4](
-vec2(0, 0), vec2(0, 1),
-vec2(1, 0), vec2(1, 1)
-);
+int face_id;
 
-const int uv_indices[12] = int[12](
-1, 0, 2, 1, 2, 3,  // tex coords indices for vertices of an even face
-3, 0, 2, 3, 1, 0   // odd face
-);
-
-vec3 hash31(float p) {
-    vec3 p3 = fract(vec3(p * 21.2) * vec3(0.1031, 0.1030, 0.0973));
-    p3 += dot(p3, p3.yzx + 33.33);
-    return fract((p3.xxy + p3.yzz) * p3.zyx) + 0.05;
+// UV з позиції вершини в площині грані: на гранях, злитих greedy-мешером,
+// текстура повторюється раз на блок. Орієнтація та сама, що була в uv_indices
+vec2 face_uv(vec3 pos) {
+    switch (face_id) {
+        case 0: return vec2(pos.x, -pos.z);  // top
+        case 1: return vec2(-pos.x, -pos.z); // bottom
+        case 2: return vec2(pos.z, -pos.y);  // right
+        case 3: return vec2(-pos.z, -pos.y); // left
+        case 4: return vec2(pos.x, -pos.y);  // back
+        default: return vec2(-pos.x, -pos.y); // front
+    }
 }
 
+const float MODEL_UNITS = 16.0; // Частки блока в позиції, як у blocks/model.rs
+const int PAGE_VERTICES = 64;   // Як у chunk_arena.rs
+
 void main() {
-    int uv_index = gl_VertexID % 6  + (face_id & 1) * 6;
-    uv = uv_coords[uv_indices[uv_index]];
-    voxel_color = hash31(voxel_id);
-    gl_Position = m_proj * m_view * m_model * vec4(in_position, 1.0);
+    uint data = packed_vertex.x;
+    vec3 in_position = vec3(data & 1023u, (data >> 10) & 1023u, (data >> 20) & 1023u) / MODEL_UNITS;
+    data = packed_vertex.y;
+    int voxel_id = int(data & 0xFFFFu);
+    face_id = int((data >> 16) & 7u);
+    int ao_id = int((data >> 19) & 3u); // 0 — кут закритий, 3 — відкритий
+    int light_level = int((data >> 21) & 15u);
+    texture_layer = int((data >> 25) & 127u);
+
+    uv = face_uv(in_position);
+    voxel_color = block_colors[min(voxel_id, 255)];
+    ao = float(ao_id) / 3.0;
+    light = float(light_level) / 15.0;
+    // gl_VertexID уже враховує base vertex, тобто це номер вершини в усій арені
+    vec3 chunk_origin = texelFetch(u_page_origins, gl_VertexID / PAGE_VERTICES).xyz;
+    gl_Position = m_proj * m_view * vec4(chunk_origin + in_position, 1.0);
 }
\ No newline at end of file
@@ -0,0 +1,100 @@
+use crate::settings::{GeneratorKind, Settings, WorldHeight};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+pub const LEVEL_VERSION: u32 = 1;
+
+// Перший рядок файлу — контрольна сума решти: "# crc32 = 0123abcd"
+const CHECKSUM_PREFIX: &str = "# crc32 = ";
+
+// Метадані світу (level.toml): з чим його створено і де гравець зупинився
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Level {
+    pub version: u32,
+    pub seed: u32,
+    pub game_time: f64, // Секунди гри в цьому світі
+    pub world_height: WorldHeight,
+    pub player: PlayerState,
+    pub generator: GeneratorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlayerState {
+    pub position: [f32; 3],
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl Level {
+    // Новий світ з параметрами з налаштувань; гравець — у стартовій позиції
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            version: LEVEL_VERSION,
+            seed: settings.world_seed,
+            game_time: 0.0,
+            world_height: settings.world_height,
+            player: PlayerState {
+                position: settings.player_pos.to_array(),
+                yaw: (-90.0f32).to_radians(),
+                pitch: 0.0,
+            },
+            generator: settings.generator.clone(),
+        }
+    }
+
+    // Світ генерується з тими ж параметрами, з якими його створено
+    pub fn apply_to(&self, settings: &mut Settings) {
+        settings.world_seed = self.seed;
+        settings.world_height = self.world_height;
+        settings.generator = self.generator.clone();
+    }
+}
+
+// None — файлу ще немає (новий світ). Пошкоджений файл дає InvalidData
+pub fn load_level(path: &Path) -> io::Result<Option<Level>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let (header, body) = text.split_once('\n').unwrap_or((&text, ""));
+    let checksum = header
+        .strip_prefix(CHECKSUM_PREFIX)
+        .and_then(|hex| u32::from_str_radix(hex.trim(), 16).ok())
+        .ok_or_else(|| invalid(path, "missing checksum"))?;
+    if checksum != crc32fast::hash(body.as_bytes()) {
+        return Err(invalid(path, "checksum mismatch"));
+    }
+    let level: Level = toml::from_str(body).map_err(|err| invalid(path, err.message()))?;
+    if level.version != LEVEL_VERSION {
+        return Err(invalid(
+            path,
+            &format!("unsupported level version {}", level.version),
+        ));
+    }
+    Ok(Some(level))
+}
+
+// Пише в тимчасовий файл і перейменовує, щоб обірваний запис не зіпсував старий
+pub fn save_level(path: &Path, level: &Level) -> io::Result<()> {
+    let body = toml::to_string(level).map_err(|err| invalid(path, &err.to_string()))?;
+    let text = format!(
+        "{}{:08x}\n{}",
+        CHECKSUM_PREFIX,
+        crc32fast::hash(body.as_bytes()),
+        body
+    );
+    let temp_path = path.with_extension("toml.tmp");
+    std::fs::write(&temp_path, text)?;
+    std::fs::rename(&temp_path, path)
+}
+
+fn invalid(path: &Path, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), message),
+    )
+}
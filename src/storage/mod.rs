@@ -0,0 +1,90 @@
+pub mod level;
+pub mod region;
+
+pub use level::{LEVEL_VERSION, Level, PlayerState, load_level, save_level};
+pub use region::{REGION_HEIGHT, REGION_SIZE, REGION_VERSION, RegionFile};
+
+use crate::blocks::BlockId;
+use glam::IVec3;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// Вміст каталогу світу
+pub const LEVEL_FILE: &str = "level.toml";
+pub const REGION_DIR: &str = "region";
+
+// Чанки світу на диску: каталог з файлами регіонів r.<x>.<y>.<z>.vxr.
+// Спільний для потоку запису і потоків генерації (читання)
+pub struct RegionStore {
+    dir: PathBuf,
+    regions: Mutex<HashMap<IVec3, RegionFile>>,
+}
+
+impl RegionStore {
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            regions: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn region_path(&self, region_pos: IVec3) -> PathBuf {
+        self.dir.join(format!(
+            "r.{}.{}.{}.vxr",
+            region_pos.x, region_pos.y, region_pos.z
+        ))
+    }
+
+    // Файл регіону створюється лише під час запису; читання з неіснуючого дає None
+    fn with_region<T>(
+        &self,
+        chunk_pos: IVec3,
+        create: bool,
+        f: impl FnOnce(&mut RegionFile) -> io::Result<Option<T>>,
+    ) -> io::Result<Option<T>> {
+        let region_pos = RegionFile::region_pos(chunk_pos);
+        let mut regions = self.regions.lock().unwrap();
+        let region = match regions.entry(region_pos) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let path = self.region_path(region_pos);
+                if !create && !path.exists() {
+                    return Ok(None);
+                }
+                let region = RegionFile::open(&path).map_err(|err| {
+                    io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+                })?;
+                entry.insert(region)
+            }
+        };
+        f(region)
+    }
+
+    // None — чанк ще не зберігався, його треба згенерувати
+    pub fn load_chunk(&self, chunk_pos: IVec3) -> io::Result<Option<Vec<BlockId>>> {
+        self.with_region(chunk_pos, false, |region| region.read_chunk(chunk_pos))
+    }
+
+    pub fn save_chunk(&self, chunk_pos: IVec3, voxels: &[BlockId]) -> io::Result<()> {
+        self.with_region(chunk_pos, true, |region| {
+            region.write_chunk(chunk_pos, voxels).map(Some)
+        })?;
+        Ok(())
+    }
+
+    pub fn flush(&self) -> io::Result<()> {
+        for region in self.regions.lock().unwrap().values_mut() {
+            region.flush()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+use crate::blocks::BlockId;
+use crate::settings::CHUNK_VOL;
+use glam::IVec3;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+// Регіон — REGION_SIZE x REGION_SIZE колонок чанків і REGION_HEIGHT чанків по Y в одному файлі
+pub const REGION_SIZE: i32 = 32;
+pub const REGION_HEIGHT: i32 = 8;
+const REGION_AREA: i32 = REGION_SIZE * REGION_SIZE;
+const REGION_CHUNKS: usize = (REGION_AREA * REGION_HEIGHT) as usize;
+
+// Формат файлу:
+//   "VXRG", версія (u32 LE)
+//   таблиця на REGION_CHUNKS записів: (перший сектор u32 LE, довжина в байтах u32 LE),
+//     нульовий сектор — чанк не збережено
+//   записи чанків, кожен з початку сектора: CRC32 (u32 LE) і стиснені LZ4 воксели
+const MAGIC: &[u8; 4] = b"VXRG";
+pub const REGION_VERSION: u32 = 2;
+const ENTRY_SIZE: u64 = 8;
+const HEADER_SIZE: u64 = 8 + REGION_CHUNKS as u64 * ENTRY_SIZE;
+const SECTOR_SIZE: u64 = 4096;
+const FIRST_DATA_SECTOR: u32 = HEADER_SIZE.div_ceil(SECTOR_SIZE) as u32;
+// Із запасом більше за найгірший результат LZ4 для чанка; довші записи — пошкоджені
+const MAX_RECORD_LENGTH: u32 = 2 * CHUNK_VOL;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Entry {
+    sector: u32,
+    length: u32,
+}
+
+impl Entry {
+    fn sectors(&self) -> u32 {
+        (self.length as u64).div_ceil(SECTOR_SIZE) as u32
+    }
+
+    // Запис починається після заголовка, має розумну довжину і цілком лежить у файлі
+    fn is_valid(&self, file_len: u64) -> bool {
+        self.sector >= FIRST_DATA_SECTOR
+            && (4..=MAX_RECORD_LENGTH).contains(&self.length)
+            && self.sector as u64 * SECTOR_SIZE + self.length as u64 <= file_len
+    }
+}
+
+pub struct RegionFile {
+    file: File,
+    entries: Vec<Entry>,
+    // Перший вільний сектор у кінці файлу
+    end_sector: u32,
+    // Вільні проміжки (перший сектор, кількість) між записами
+    free: Vec<(u32, u32)>,
+    // Сектори старих версій чанків. Таблиця вже вказує в інше місце, але на диску
+    // це може ще не опинитися, тож перевикористовуються вони лише після flush
+    released: Vec<(u32, u32)>,
+}
+
+impl RegionFile {
+    // Регіон, у якому лежить чанк
+    pub fn region_pos(chunk_pos: IVec3) -> IVec3 {
+        IVec3::new(
+            chunk_pos.x.div_euclid(REGION_SIZE),
+            chunk_pos.y.div_euclid(REGION_HEIGHT),
+            chunk_pos.z.div_euclid(REGION_SIZE),
+        )
+    }
+
+    // Індекс чанка в таблиці, в тому ж порядку, що й воксели в чанку
+    fn entry_index(chunk_pos: IVec3) -> usize {
+        let x = chunk_pos.x.rem_euclid(REGION_SIZE);
+        let y = chunk_pos.y.rem_euclid(REGION_HEIGHT);
+        let z = chunk_pos.z.rem_euclid(REGION_SIZE);
+        (x + REGION_SIZE * z + REGION_AREA * y) as usize
+    }
+
+    // Відкриває файл регіону або створює порожній
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let file_len = file.metadata()?.len();
+        if file_len == 0 {
+            let mut header = Vec::with_capacity(HEADER_SIZE as usize);
+            header.extend_from_slice(MAGIC);
+            header.extend_from_slice(&REGION_VERSION.to_le_bytes());
+            header.resize(HEADER_SIZE as usize, 0);
+            file.write_all(&header)?;
+            return Ok(Self {
+                file,
+                entries: vec![Entry::default(); REGION_CHUNKS],
+                end_sector: FIRST_DATA_SECTOR,
+                free: Vec::new(),
+                released: Vec::new(),
+            });
+        }
+
+        let mut header = vec![0u8; HEADER_SIZE as usize];
+        file.read_exact(&mut header).map_err(|_| invalid("truncated region header"))?;
+        if &header[0..4] != MAGIC {
+            return Err(invalid("not a region file"));
+        }
+        let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        if version != REGION_VERSION {
+            return Err(invalid(&format!("unsupported region version {}", version)));
+        }
+
+        let mut entries: Vec<Entry> = header[8..]
+            .chunks_exact(ENTRY_SIZE as usize)
+            .map(|bytes| Entry {
+                sector: u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+                length: u32::from_le_bytes(bytes[4..8].try_into().unwrap()),
+            })
+            .collect();
+        // Пошкоджений запис у таблиці міг би зсунути кінець файлу майже до u32::MAX,
+        // тож такі чанки вважаємо незбереженими: їх буде згенеровано заново
+        let mut cleared = 0;
+        for entry in entries.iter_mut().filter(|entry| entry.sector != 0) {
+            if !entry.is_valid(file_len) {
+                *entry = Entry::default();
+                cleared += 1;
+            }
+        }
+        if cleared > 0 {
+            eprintln!(
+                "{}: {} corrupt chunk entries cleared",
+                path.display(),
+                cleared
+            );
+        }
+
+        // Проміжки між записами лишилися від старих версій чанків
+        let mut used: Vec<(u32, u32)> = entries
+            .iter()
+            .filter(|entry| entry.sector != 0)
+            .map(|entry| (entry.sector, entry.sectors()))
+            .collect();
+        used.sort_unstable();
+        let mut free = Vec::new();
+        let mut end_sector = FIRST_DATA_SECTOR;
+        for (sector, sectors) in used {
+            if sector > end_sector {
+                free.push((end_sector, sector - end_sector));
+            }
+            end_sector = end_sector.max(sector + sectors);
+        }
+        Ok(Self {
+            file,
+            entries,
+            end_sector,
+            free,
+            released: Vec::new(),
+        })
+    }
+
+    pub fn read_chunk(&mut self, chunk_pos: IVec3) -> io::Result<Option<Vec<BlockId>>> {
+        let entry = self.entries[Self::entry_index(chunk_pos)];
+        if entry.sector == 0 {
+            return Ok(None);
+        }
+        if entry.length < 4 || entry.length > MAX_RECORD_LENGTH {
+            return Err(invalid(&format!(
+                "chunk {}: bad record length {}",
+                chunk_pos, entry.length
+            )));
+        }
+        let mut record = vec![0u8; entry.length as usize];
+        self.file.seek(SeekFrom::Start(entry.sector as u64 * SECTOR_SIZE))?;
+        self.file
+            .read_exact(&mut record)
+            .map_err(|_| invalid(&format!("chunk {}: truncated record", chunk_pos)))?;
+        let (checksum, compressed) = record.split_at(4);
+        if u32::from_le_bytes(checksum.try_into().unwrap()) != crc32fast::hash(compressed) {
+            return Err(invalid(&format!("chunk {}: checksum mismatch", chunk_pos)));
+        }
+        let voxels = lz4_flex::decompress_size_prepended(compressed)
+            .map_err(|err| invalid(&format!("chunk {}: {}", chunk_pos, err)))?;
+        if voxels.len() != CHUNK_VOL as usize {
+            return Err(invalid(&format!(
+                "chunk {}: {} voxels instead of {}",
+                chunk_pos,
+                voxels.len(),
+                CHUNK_VOL
+            )));
+        }
+        Ok(Some(voxels))
+    }
+
+    // Нова версія чанка завжди пишеться у вільні сектори, а не поверх старої: обірваний
+    // запис не має зіпсувати єдину збережену копію
+    pub fn write_chunk(&mut self, chunk_pos: IVec3, voxels: &[BlockId]) -> io::Result<()> {
+        let compressed = lz4_flex::compress_prepend_size(voxels);
+        let mut record = Vec::with_capacity(4 + compressed.len());
+        record.extend_from_slice(&crc32fast::hash(&compressed).to_le_bytes());
+        record.extend_from_slice(&compressed);
+
+        let index = Self::entry_index(chunk_pos);
+        let old = self.entries[index];
+        let length = record.len() as u32;
+        let entry = Entry {
+            sector: self.allocate(length.div_ceil(SECTOR_SIZE as u32)),
+            length,
+        };
+
+        self.file.seek(SeekFrom::Start(entry.sector as u64 * SECTOR_SIZE))?;
+        self.file.write_all(&record)?;
+        // Запис у таблиці — після даних, щоб обірваний запис не вказував на сміття
+        let mut entry_bytes = [0u8; ENTRY_SIZE as usize];
+        entry_bytes[0..4].copy_from_slice(&entry.sector.to_le_bytes());
+        entry_bytes[4..8].copy_from_slice(&entry.length.to_le_bytes());
+        self.file.seek(SeekFrom::Start(8 + index as u64 * ENTRY_SIZE))?;
+        self.file.write_all(&entry_bytes)?;
+        self.entries[index] = entry;
+        if old.sector != 0 {
+            self.released.push((old.sector, old.sectors()));
+        }
+        Ok(())
+    }
+
+    // Перший вільний проміжок, куди вміщується запис, або кінець файлу
+    fn allocate(&mut self, sectors: u32) -> u32 {
+        let Some(slot) = self.free.iter().position(|&(_, count)| count >= sectors) else {
+            let sector = self.end_sector;
+            self.end_sector += sectors;
+            return sector;
+        };
+        let (sector, count) = self.free[slot];
+        if count == sectors {
+            self.free.swap_remove(slot);
+        } else {
+            self.free[slot] = (sector + sectors, count - sectors);
+        }
+        sector
+    }
+
+    // Дочікується запису на диск. Після цього сектори старих версій чанків вільні
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.sync_data()?;
+        self.free.append(&mut self.released);
+        Ok(())
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
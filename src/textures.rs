@@ -1,42 +1,90 @@
-use image::{DynamicImage, GenericImageView};
+use image::GenericImageView;
+use image::imageops::FilterType;
 use sdl2::video::GLContext;
+use voxel_engine::blocks::BlockRegistry;
+
+const TEXTURE_DIR: &str = "src/assets";
+// Якщо в реєстрі немає жодної текстури, шар 0 однаково має існувати
+const FALLBACK_TEXTURE: &str = "frame";
 
 pub struct Textures {
     texture_0: u32,
 }
 
 impl Textures {
-    pub fn new(_gl_context: &GLContext) -> Self {
-        let img = image::open("src/assets/frame.png").expect("Failed to load texture");
-        let (width, height) = img.dimensions();
-        let data = img.to_rgba8().into_raw();
+    // Масив текстур: шар = індекс у BlockRegistry::texture_names, його передає вершина чанка.
+    // Усі шари масиву однакового розміру, тож решту зображень підганяємо під перше
+    pub fn new(_gl_context: &GLContext, registry: &BlockRegistry) -> Self {
+        let mut names: Vec<&str> = registry
+            .texture_names()
+            .iter()
+            .map(String::as_str)
+            .collect();
+        if names.is_empty() {
+            names.push(FALLBACK_TEXTURE);
+        }
+        let images: Vec<_> = names
+            .iter()
+            .map(|name| {
+                let path = format!("{}/{}.png", TEXTURE_DIR, name);
+                image::open(&path)
+                    .unwrap_or_else(|err| panic!("Failed to load texture {}: {}", path, err))
+            })
+            .collect();
+        let (width, height) = images[0].dimensions();
+        let mut data = Vec::with_capacity((width * height * 4) as usize * images.len());
+        for img in &images {
+            let img = if img.dimensions() == (width, height) {
+                img.to_rgba8()
+            } else {
+                img.resize_exact(width, height, FilterType::Nearest)
+                    .to_rgba8()
+            };
+            data.extend_from_slice(img.as_raw());
+        }
 
         let mut texture_0 = 0;
         unsafe {
             gl::GenTextures(1, &mut texture_0);
-            gl::BindTexture(gl::TEXTURE_2D, texture_0);
-            gl::TexImage2D(
-                gl::TEXTURE_2D,
+            gl::BindTexture(gl::TEXTURE_2D_ARRAY, texture_0);
+            gl::TexImage3D(
+                gl::TEXTURE_2D_ARRAY,
                 0,
                 gl::RGBA as i32,
                 width as i32,
                 height as i32,
+                images.len() as i32,
                 0,
                 gl::RGBA,
                 gl::UNSIGNED_BYTE,
                 data.as_ptr() as *const _,
             );
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::NEAREST as i32);
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::NEAREST as i32);
-            gl::GenerateMipmap(gl::TEXTURE_2D);
-            gl::BindTexture(gl::TEXTURE_2D, 0);
+            // Злиті грані greedy-мешера повторюють текстуру
+            gl::TexParameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
+            gl::TexParameteri(gl::TEXTURE_2D_ARRAY, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
+            gl::TexParameteri(
+                gl::TEXTURE_2D_ARRAY,
+                gl::TEXTURE_MIN_FILTER,
+                gl::NEAREST as i32,
+            );
+            gl::TexParameteri(
+                gl::TEXTURE_2D_ARRAY,
+                gl::TEXTURE_MAG_FILTER,
+                gl::NEAREST as i32,
+            );
+            gl::GenerateMipmap(gl::TEXTURE_2D_ARRAY);
+            gl::BindTexture(gl::TEXTURE_2D_ARRAY, 0);
         }
 
+        let textures = Self { texture_0 };
+        textures.bind();
+        textures
+    }
+
+    pub fn bind(&self) {
         unsafe {
             gl::ActiveTexture(gl::TEXTURE0);
-            gl::BindTexture(gl::TEXTURE_2D, texture_0);
+            gl::BindTexture(gl::TEXTURE_2D_ARRAY, self.texture_0);
         }
-
-        Self { texture_0 }
     }
-}
\ No newline at end of file
+}
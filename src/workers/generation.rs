@@ -0,0 +1,26 @@
+use crate::generators::WorldGenerator;
+use crate::storage::RegionStore;
+use crate::workers::WorkerPool;
+use crate::world_objects::PalettedVoxels;
+use std::sync::Arc;
+
+// Воксели завантажених з диска або згенерованих чанків, вже стиснені в палітру
+pub type GenerationPool = WorkerPool<(), PalettedVoxels>;
+
+pub fn generation_pool(
+    generator: Arc<dyn WorldGenerator>,
+    storage: Option<Arc<RegionStore>>,
+    threads: usize,
+) -> GenerationPool {
+    WorkerPool::new("chunk-gen", threads, move |chunk_pos, ()| {
+        if let Some(storage) = &storage {
+            match storage.load_chunk(chunk_pos) {
+                Ok(Some(voxels)) => return PalettedVoxels::from_voxels(&voxels),
+                Ok(None) => {}
+                // Пошкоджений чанк генеруємо заново, щоб світ лишався придатним
+                Err(err) => eprintln!("Failed to load chunk {}: {}", chunk_pos, err),
+            }
+        }
+        PalettedVoxels::from_voxels(&generator.generate(chunk_pos))
+    })
+}
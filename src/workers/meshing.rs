@@ -0,0 +1,35 @@
+use crate::blocks::BlockRegistry;
+use crate::meshes::chunk_mesh::ChunkMesh;
+use crate::settings::{MesherKind, UnloadedChunkPolicy, WorldHeight};
+use crate::workers::WorkerPool;
+use crate::world_objects::{Chunk, ChunkNeighborhood};
+use std::sync::Arc;
+
+// Незмінний знімок чанка і його завантажених сусідів на момент постановки завдання.
+// Воксели спільні з World через Arc, тож знімок не копіює даних.
+pub struct MeshJob {
+    pub chunks: Vec<Chunk>, // Перший — сам чанк
+    pub version: u64,
+    pub unloaded: UnloadedChunkPolicy,
+    pub world_height: WorldHeight,
+    pub lod: u8,
+}
+
+// Результат несе версію чанка, з якої він побудований,
+// щоб World міг відкинути застарілий меш
+pub type MeshingPool = WorkerPool<MeshJob, (u64, ChunkMesh)>;
+
+pub fn meshing_pool(
+    registry: Arc<BlockRegistry>,
+    mesher: MesherKind,
+    threads: usize,
+) -> MeshingPool {
+    WorkerPool::new("chunk-mesh", threads, move |_, job: MeshJob| {
+        let (chunk, neighbors) = job.chunks.split_first().unwrap();
+        let neighborhood = ChunkNeighborhood::new(chunk, job.world_height, |pos| {
+            neighbors.iter().find(|neighbor| neighbor.position == pos)
+        });
+        let mesh = ChunkMesh::new(&neighborhood, &registry, job.unloaded, mesher, job.lod);
+        (job.version, mesh)
+    })
+}
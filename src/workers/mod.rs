@@ -0,0 +1,9 @@
+pub mod generation;
+pub mod meshing;
+pub mod pool;
+pub mod saving;
+
+pub use generation::{GenerationPool, generation_pool};
+pub use meshing::{MeshJob, MeshingPool, meshing_pool};
+pub use pool::WorkerPool;
+pub use saving::{SavingPool, saving_pool};
@@ -0,0 +1,169 @@
+use glam::IVec3;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+
+// Номер запиту; робітник повертає його разом із результатом
+type Ticket = u64;
+
+struct Queue<J> {
+    jobs: VecDeque<(IVec3, Ticket, J)>,
+    shutdown: bool,
+}
+
+struct Shared<J> {
+    queue: Mutex<Queue<J>>,
+    available: Condvar,
+}
+
+// Пул потоків для роботи над чанками. Завдання адресуються позицією чанка,
+// результати повертаються через канал; усі методи, крім wait_finished,
+// не блокують викликаючий потік.
+pub struct WorkerPool<J, R> {
+    shared: Arc<Shared<J>>,
+    results: Receiver<(IVec3, Ticket, R)>,
+    // Квитки запитів, чиї результати ще очікуються, для кожного чанка. Результат
+    // з квитком поза цією мапою (скасований чи замінений запит) відкидається, навіть
+    // якщо для того самого чанка вже чекає новіший запит
+    pending: HashMap<IVec3, Vec<Ticket>>,
+    next_ticket: Ticket,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl<J: Send + 'static, R: Send + 'static> WorkerPool<J, R> {
+    pub fn new(
+        name: &str,
+        threads: usize,
+        work: impl Fn(IVec3, J) -> R + Send + Sync + 'static,
+    ) -> Self {
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue {
+                jobs: VecDeque::new(),
+                shutdown: false,
+            }),
+            available: Condvar::new(),
+        });
+        let work = Arc::new(work);
+        let (sender, results) = channel();
+        let workers = (0..threads.max(1))
+            .map(|i| {
+                let shared = shared.clone();
+                let work = work.clone();
+                let sender = sender.clone();
+                std::thread::Builder::new()
+                    .name(format!("{}-{}", name, i))
+                    .spawn(move || worker_loop(&shared, work.as_ref(), &sender))
+                    .unwrap()
+            })
+            .collect();
+        Self {
+            shared,
+            results,
+            pending: HashMap::new(),
+            next_ticket: 0,
+            workers,
+        }
+    }
+
+    // Якщо завдання для цього чанка ще чекає в черзі, воно замінюється новим
+    pub fn request(&mut self, chunk_pos: IVec3, job: J) {
+        let mut queue = self.shared.queue.lock().unwrap();
+        if let Some(queued) = queue.jobs.iter_mut().find(|(pos, ..)| *pos == chunk_pos) {
+            queued.2 = job;
+            return;
+        }
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+        queue.jobs.push_back((chunk_pos, ticket, job));
+        drop(queue);
+        self.pending.entry(chunk_pos).or_default().push(ticket);
+        self.shared.available.notify_one();
+    }
+
+    pub fn is_pending(&self, chunk_pos: IVec3) -> bool {
+        self.pending.contains_key(&chunk_pos)
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    // Скасовує завдання, для яких keep повертає false. Ще не взяті в роботу
+    // прибираються з черги, а результати вже запущених будуть відкинуті.
+    pub fn cancel(&mut self, keep: impl Fn(IVec3) -> bool) {
+        self.pending.retain(|pos, _| keep(*pos));
+        self.shared.queue.lock().unwrap().jobs.retain(|(pos, ..)| keep(*pos));
+    }
+
+    // Усі готові на цей момент результати, без очікування
+    pub fn try_finished(&mut self) -> Vec<(IVec3, R)> {
+        let mut finished = Vec::new();
+        while let Ok((chunk_pos, ticket, result)) = self.results.try_recv() {
+            if self.complete(chunk_pos, ticket) {
+                finished.push((chunk_pos, result));
+            }
+        }
+        finished
+    }
+
+    // Чекає на наступний очікуваний результат; None, якщо чекати нічого
+    pub fn wait_finished(&mut self) -> Option<(IVec3, R)> {
+        while !self.pending.is_empty() {
+            let (chunk_pos, ticket, result) = self.results.recv().ok()?;
+            if self.complete(chunk_pos, ticket) {
+                return Some((chunk_pos, result));
+            }
+        }
+        None
+    }
+
+    fn complete(&mut self, chunk_pos: IVec3, ticket: Ticket) -> bool {
+        let Some(tickets) = self.pending.get_mut(&chunk_pos) else {
+            return false;
+        };
+        let Some(index) = tickets.iter().position(|pending| *pending == ticket) else {
+            return false;
+        };
+        tickets.swap_remove(index);
+        if tickets.is_empty() {
+            self.pending.remove(&chunk_pos);
+        }
+        true
+    }
+}
+
+impl<J, R> Drop for WorkerPool<J, R> {
+    fn drop(&mut self) {
+        self.shared.queue.lock().unwrap().shutdown = true;
+        self.shared.available.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn worker_loop<J, R>(
+    shared: &Shared<J>,
+    work: &(impl Fn(IVec3, J) -> R + ?Sized),
+    sender: &Sender<(IVec3, Ticket, R)>,
+) {
+    loop {
+        let (chunk_pos, ticket, job) = {
+            let mut queue = shared.queue.lock().unwrap();
+            loop {
+                if queue.shutdown {
+                    return;
+                }
+                if let Some(job) = queue.jobs.pop_front() {
+                    break job;
+                }
+                queue = shared.available.wait(queue).unwrap();
+            }
+        };
+        let result = work(chunk_pos, job);
+        if sender.send((chunk_pos, ticket, result)).is_err() {
+            return;
+        }
+    }
+}
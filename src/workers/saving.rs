@@ -0,0 +1,19 @@
+use crate::storage::RegionStore;
+use crate::workers::WorkerPool;
+use crate::world_objects::PalettedVoxels;
+use std::io;
+use std::sync::Arc;
+
+// Запис змінених чанків на диск. Один потік: записи в регіони йдуть по черзі,
+// тож новіша версія чанка не може бути перезаписана старішою
+pub type SavingPool = WorkerPool<Arc<PalettedVoxels>, io::Result<()>>;
+
+pub fn saving_pool(storage: Arc<RegionStore>) -> SavingPool {
+    WorkerPool::new(
+        "chunk-save",
+        1,
+        move |chunk_pos, voxels: Arc<PalettedVoxels>| {
+            storage.save_chunk(chunk_pos, &voxels.to_vec())
+        },
+    )
+}
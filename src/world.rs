@@ -1,43 +1,332 @@
-use crate::player::Player;
-use crate::settings::{CHUNK_AREA, CHUNK_SIZE, CHUNK_VOL, MAX_Y, MIN_Y, Settings};
-use crate::shader_program::ShaderProgram;
-use crate::world_objects::Chunk;
-use glam::IVec3;
-use noise::{NoiseFn, Simplex};
-use rand::Rng;
+use crate::blocks::{Block, BlockId, BlockRegistry};
+use crate::generators::{WorldGenerator, create_generator};
+use crate::meshes::chunk_mesh::ChunkMesh;
+use crate::settings::{CHUNK_SIZE, Settings, UnloadedChunkPolicy, WorldHeight};
+use crate::storage::{REGION_DIR, RegionStore};
+use crate::workers::{
+    GenerationPool, MeshJob, MeshingPool, SavingPool, generation_pool, meshing_pool, saving_pool,
+};
+use crate::world_objects::neighborhood::{FACE_NEIGHBORS, neighbor_offsets};
+use crate::world_objects::{Chunk, ChunkFill, ChunkNeighborhood, PalettedVoxels};
+use glam::{IVec3, Vec3};
 use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+
+// Скільки завдань на потік може одночасно чекати в пулі. Решта лишається
+// в черзі World, де її можна перевпорядкувати, коли гравець рухається чи обертається
+const JOBS_PER_THREAD: usize = 2;
+
+// Косинус кута, на який має повернутися камера, щоб черга пересортувалася
+const VIEW_RESORT_COS: f32 = 0.985; // ~10°
+
+// Довжини черг стрімінгу для налаштування бюджетів
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StreamingStats {
+    pub load_queue: usize,   // Чанки, які ще не віддані на генерацію
+    pub generating: usize,   // У пулі генерації
+    pub mesh_queue: usize,   // Брудні чанки, які ще не віддані на мешинг
+    pub meshing: usize,      // У пулі мешингу
+    pub saving: usize,       // Змінені чанки, які ще пишуться на диск
+    pub upload_queue: usize, // Готові меші, які ще не забрав рендерер
+    pub empty_chunks: usize,  // Без меша: лише повітря
+    pub buried_chunks: usize, // Без меша: непрозорі й закриті непрозорими сусідами
+}
 
 pub struct World {
     pub(crate) chunks: HashMap<IVec3, Chunk>,
-    shader_program: ShaderProgram,
     pub render_distance: i32,
-    world_seed: u32
+    lod_distances: [i32; 3],
+    world_height: WorldHeight,
+    pub unloaded_chunks: UnloadedChunkPolicy,
+    registry: Arc<BlockRegistry>,
+    generation: GenerationPool,
+    meshing: MeshingPool,
+    // Змінені чанки пишуться сюди при вивантаженні і в save_modified
+    storage: Option<Arc<RegionStore>>,
+    // Запис іде у фоні, щоб не тримати м'ютекс регіонів у потоці рендеру
+    saving: Option<SavingPool>,
+    // Воксели, віддані на запис, доки він не завершився: вивантажений чанк, що
+    // знову знадобився, береться звідси, бо на диску може бути ще стара версія
+    unsaved: HashMap<IVec3, Arc<PalettedVoxels>>,
+    center_pos: IVec3,
+    player_pos: Vec3,
+    view_dir: Vec3,
+    // Відсутні чанки, відсортовані так, що найважливіший — останній
+    load_queue: Vec<IVec3>,
+    generation_per_frame: usize,
+    meshing_per_frame: usize,
+    max_generation_jobs: usize,
+    max_meshing_jobs: usize,
 }
 
 impl World {
-    pub fn new(shader_program: &ShaderProgram, settings: &Settings) -> Self {
+    // Генератор обирається за settings.generator
+    pub fn new(settings: &Settings, registry: Arc<BlockRegistry>) -> Self {
+        let generator = create_generator(
+            &settings.generator,
+            settings.world_seed,
+            settings.world_height,
+            &registry,
+        );
+        Self::with_generator(settings, registry, generator)
+    }
+
+    pub fn with_generator(
+        settings: &Settings,
+        registry: Arc<BlockRegistry>,
+        generator: Arc<dyn WorldGenerator>,
+    ) -> Self {
+        // Без каталогу світ працює, але нічого не зберігає
+        let storage = settings.world_dir.as_ref().and_then(|dir| {
+            RegionStore::open(dir.join(REGION_DIR))
+                .map_err(|err| eprintln!("Failed to open world {}: {}", dir.display(), err))
+                .ok()
+                .map(Arc::new)
+        });
+
+        // Чанки генеруються і мешуються у фоні: їх можна дочекатися
+        // через wait_for_generation і wait_for_meshes
         let mut world = Self {
             chunks: HashMap::new(),
-            shader_program: shader_program.clone(),
             render_distance: settings.render_distance,
-            world_seed: settings.world_seed,
+            lod_distances: settings.lod_distances,
+            world_height: settings.world_height,
+            unloaded_chunks: settings.unloaded_chunks,
+            meshing: meshing_pool(registry.clone(), settings.mesher, settings.meshing_threads),
+            registry,
+            generation: generation_pool(generator, storage.clone(), settings.generation_threads),
+            saving: storage.clone().map(saving_pool),
+            unsaved: HashMap::new(),
+            storage,
+            center_pos: IVec3::ZERO,
+            player_pos: Vec3::ZERO,
+            view_dir: Vec3::NEG_Z,
+            load_queue: Vec::new(),
+            generation_per_frame: settings.chunks_generated_per_frame,
+            meshing_per_frame: settings.chunks_meshed_per_frame,
+            max_generation_jobs: settings.generation_threads.max(1) * JOBS_PER_THREAD,
+            max_meshing_jobs: settings.meshing_threads.max(1) * JOBS_PER_THREAD,
         };
         world.build_chunks(IVec3::ZERO);
         world
     }
 
-    pub fn update(&mut self, player: &Player) {
-        let player_chunk_pos = Self::world_to_chunk_pos(player.camera.position);
-        self.build_chunks(player_chunk_pos);
+    // Не блокує: забирає готові від воркерів результати і віддає в роботу
+    // найважливіші чанки в межах бюджетів на кадр
+    pub fn update(&mut self, player_pos: Vec3, view_dir: Vec3) {
+        self.player_pos = player_pos;
+        let player_chunk_pos = Self::world_to_chunk_pos(player_pos);
+        let moved = player_chunk_pos != self.center_pos;
+        // Напрям, за яким сортується черга, оновлюється при кожному пересортуванні,
+        // зокрема й коли гравець переходить в інший чанк
+        if moved || view_dir.dot(self.view_dir) < VIEW_RESORT_COS {
+            self.view_dir = view_dir;
+            if moved {
+                self.build_chunks(player_chunk_pos);
+            } else {
+                self.sort_load_queue();
+            }
+        }
+
+        self.request_chunks(self.generation_per_frame);
+        for (chunk_pos, voxels) in self.generation.try_finished() {
+            self.insert_chunk(chunk_pos, voxels);
+        }
+        self.build_dirty_meshes(self.meshing_per_frame);
+        for (chunk_pos, (version, mesh)) in self.meshing.try_finished() {
+            self.accept_mesh(chunk_pos, version, mesh);
+        }
+        if let Some(saving) = &mut self.saving {
+            for (chunk_pos, result) in saving.try_finished() {
+                if let Err(err) = result {
+                    eprintln!("Failed to save chunk {}: {}", chunk_pos, err);
+                }
+                if !saving.is_pending(chunk_pos) {
+                    self.unsaved.remove(&chunk_pos);
+                }
+            }
+        }
     }
 
-    pub fn render(&self) {
-        for chunk in self.chunks.values() {
-            chunk.render();
+    // Блокує, доки не згенеруються всі чанки в межах. Для тестів і утиліт без вікна
+    pub fn wait_for_generation(&mut self) {
+        loop {
+            self.request_chunks(usize::MAX);
+            let Some((chunk_pos, voxels)) = self.generation.wait_finished() else {
+                break;
+            };
+            self.insert_chunk(chunk_pos, voxels);
+        }
+    }
+
+    // Блокує, доки не будуть готові меші всіх змінених чанків
+    pub fn wait_for_meshes(&mut self) {
+        loop {
+            // Відкинутий застарілий меш лишає чанк брудним, тож ставимо його знову
+            self.build_dirty_meshes(usize::MAX);
+            let Some((chunk_pos, (version, mesh))) = self.meshing.wait_finished() else {
+                break;
+            };
+            self.accept_mesh(chunk_pos, version, mesh);
+        }
+    }
+
+    // Віддає всі змінені чанки на запис у фоні; повертає, скільки їх було
+    pub fn save_modified(&mut self) -> usize {
+        let modified: Vec<IVec3> = self
+            .chunks
+            .values()
+            .filter(|chunk| chunk.modified)
+            .map(|chunk| chunk.position)
+            .collect();
+        for &chunk_pos in &modified {
+            let chunk = self.chunks.get_mut(&chunk_pos).unwrap();
+            chunk.modified = false;
+            let voxels = chunk.voxels.clone();
+            self.save_chunk(chunk_pos, voxels);
+        }
+        modified.len()
+    }
+
+    // Блокує, доки не запишуться всі віддані на запис чанки. Викликається при виході
+    pub fn wait_for_saves(&mut self) -> io::Result<()> {
+        let (Some(storage), Some(saving)) = (&self.storage, &mut self.saving) else {
+            return Ok(());
+        };
+        let mut first_error = None;
+        while let Some((chunk_pos, result)) = saving.wait_finished() {
+            if let Err(err) = result {
+                first_error.get_or_insert(io::Error::new(
+                    err.kind(),
+                    format!("chunk {}: {}", chunk_pos, err),
+                ));
+            }
+        }
+        self.unsaved.clear();
+        match first_error {
+            Some(err) => Err(err),
+            None => storage.flush(),
+        }
+    }
+
+    fn save_chunk(&mut self, chunk_pos: IVec3, voxels: Arc<PalettedVoxels>) {
+        if let Some(saving) = &mut self.saving {
+            self.unsaved.insert(chunk_pos, voxels.clone());
+            saving.request(chunk_pos, voxels);
+        }
+    }
+
+    // Задається при створенні: від неї залежить і генератор
+    pub fn world_height(&self) -> WorldHeight {
+        self.world_height
+    }
+
+    pub fn stats(&self) -> StreamingStats {
+        StreamingStats {
+            load_queue: self.load_queue.len(),
+            generating: self.generation.pending_count(),
+            mesh_queue: self.chunks.values().filter(|chunk| chunk.is_dirty).count(),
+            meshing: self.meshing.pending_count(),
+            saving: self.unsaved.len(),
+            upload_queue: self.chunks.values().filter(|chunk| chunk.mesh.is_some()).count(),
+            empty_chunks: self.count_skipped(ChunkFill::Empty),
+            buried_chunks: self.count_skipped(ChunkFill::Full),
+        }
+    }
+
+    fn count_skipped(&self, fill: ChunkFill) -> usize {
+        self.chunks
+            .values()
+            .filter(|chunk| chunk.skipped && chunk.fill == fill)
+            .count()
+    }
+
+    pub fn registry(&self) -> &BlockRegistry {
+        &self.registry
+    }
+
+    // None — якщо чанк з цим вокселем не завантажений
+    pub fn get_voxel(&self, world_voxel_pos: IVec3) -> Option<BlockId> {
+        let (chunk_pos, local_pos) = Self::voxel_to_chunk_pos(world_voxel_pos);
+        Some(self.chunks.get(&chunk_pos)?.get_voxel(local_pos))
+    }
+
+    pub fn get_block(&self, world_voxel_pos: IVec3) -> Option<&Block> {
+        self.registry.get(self.get_voxel(world_voxel_pos)?)
+    }
+
+    // Для колізій: незавантажені чанки вважаються суцільними
+    pub fn is_solid(&self, world_voxel_pos: IVec3) -> bool {
+        self.get_voxel(world_voxel_pos)
+            .is_none_or(|voxel_id| self.registry.is_solid(voxel_id))
+    }
+
+    // Повертає false, якщо чанк не завантажений. Меш перебудується в наступному update
+    pub fn set_voxel(&mut self, world_voxel_pos: IVec3, voxel_id: BlockId) -> bool {
+        let (chunk_pos, local_pos) = Self::voxel_to_chunk_pos(world_voxel_pos);
+        let Some(chunk) = self.chunks.get_mut(&chunk_pos) else {
+            return false;
+        };
+        if chunk.get_voxel(local_pos) == voxel_id {
+            return true;
+        }
+        chunk.set_voxel(local_pos, voxel_id);
+        chunk.fill = ChunkFill::of(&chunk.voxels, &self.registry);
+
+        // Воксель на межі відкриває або закриває грані сусіднього чанка і змінює
+        // затінення кутів у ньому; на ребрі чи в куті — і в діагональних сусідах
+        let last = CHUNK_SIZE as i32 - 1;
+        for offset in neighbor_offsets() {
+            let touches = (0..3).all(|axis| match offset[axis] {
+                -1 => local_pos[axis] == 0,
+                1 => local_pos[axis] == last,
+                _ => true,
+            });
+            if touches {
+                self.mark_dirty(chunk_pos + offset);
+            }
         }
+        true
+    }
+
+    pub fn chunk(&self, chunk_pos: IVec3) -> Option<&Chunk> {
+        self.chunks.get(&chunk_pos)
+    }
+
+    pub fn chunks(&self) -> impl Iterator<Item = &Chunk> {
+        self.chunks.values()
+    }
+
+    // Чанк разом із завантаженими сусідами — для мешингу й запитів через межі чанків
+    pub fn neighborhood(&self, chunk_pos: IVec3) -> Option<ChunkNeighborhood<'_>> {
+        let chunk = self.chunks.get(&chunk_pos)?;
+        Some(ChunkNeighborhood::new(chunk, self.world_height, |pos| {
+            self.chunks.get(&pos)
+        }))
     }
 
-    fn world_to_chunk_pos(pos: glam::Vec3) -> IVec3 {
+    // Віддає до limit мешів, побудованих з моменту попереднього виклику, найважливіші першими
+    pub fn take_meshes(&mut self, limit: usize) -> Vec<(IVec3, ChunkMesh)> {
+        let mut ready: Vec<IVec3> = self
+            .chunks
+            .values()
+            .filter(|chunk| chunk.mesh.is_some())
+            .map(|chunk| chunk.position)
+            .collect();
+        self.sort_by_priority(&mut ready);
+        ready
+            .into_iter()
+            .rev()
+            .take(limit)
+            .filter_map(|chunk_pos| {
+                let mesh = self.chunks.get_mut(&chunk_pos)?.mesh.take()?;
+                Some((chunk_pos, mesh))
+            })
+            .collect()
+    }
+
+    pub fn world_to_chunk_pos(pos: Vec3) -> IVec3 {
         IVec3::new(
             (pos.x / CHUNK_SIZE as f32).floor() as i32,
             (pos.y / CHUNK_SIZE as f32).floor() as i32,
@@ -45,61 +334,224 @@ impl World {
         )
     }
 
+    // Позиція чанка і локальна позиція вокселя всередині нього
+    pub fn voxel_to_chunk_pos(world_voxel_pos: IVec3) -> (IVec3, IVec3) {
+        let size = IVec3::splat(CHUNK_SIZE as i32);
+        (
+            world_voxel_pos.div_euclid(size),
+            world_voxel_pos.rem_euclid(size),
+        )
+    }
+
+    fn mark_dirty(&mut self, chunk_pos: IVec3) {
+        if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
+            chunk.mark_dirty();
+        }
+    }
+
+    fn in_range(
+        render_distance: i32,
+        world_height: WorldHeight,
+        chunk_pos: IVec3,
+        center_pos: IVec3,
+    ) -> bool {
+        let rel = chunk_pos - center_pos;
+        rel.x.abs() <= render_distance
+            && rel.y.abs() <= render_distance
+            && rel.z.abs() <= render_distance
+            && world_height.contains(chunk_pos.y) // Абсолютне обмеження по висоті світу
+    }
+
     fn build_chunks(&mut self, center_pos: IVec3) {
-        // self.chunks.retain(|pos, _| {
-        //     let dist = (pos - center_pos).abs().max_element();
-        //     dist <= self.render_distance
-        // });
-
-        // for x in -self.render_distance..=self.render_distance {
-        //     for y in -self.render_distance..=self.render_distance {
-        //         for z in -self.render_distance..=self.render_distance {
-        //             let chunk_pos = center_pos + IVec3::new(x, y, z);
-        //             if !self.chunks.contains_key(&chunk_pos) {
-        //                 let voxels = self.generate_voxels(chunk_pos);
-        //                 let chunk = Chunk::new(&self.shader_program, chunk_pos, voxels, self);
-        //                 self.chunks.insert(chunk_pos, chunk);
-        //             }
-        //         }
-        //     }
-        // }
-
-        // Видаляємо чанки поза межами render_distance або нижче MIN_Y
-        // Видаляємо чанки поза межами render_distance або за межами MIN_Y/MAX_Y
-        self.chunks.retain(|pos, _| {
-            let rel_x = pos.x - center_pos.x;
-            let rel_y = pos.y - center_pos.y;
-            let rel_z = pos.z - center_pos.z;
-            rel_x >= -self.render_distance &&
-                rel_x <= self.render_distance &&
-                rel_y >= (MIN_Y - center_pos.y).max(-self.render_distance) && // Нижня межа
-                rel_y <= (MAX_Y - center_pos.y).min(self.render_distance) && // Верхня межа
-                rel_z >= -self.render_distance &&
-                rel_z <= self.render_distance &&
-                pos.y >= MIN_Y && // Абсолютне обмеження по MIN_Y
-                pos.y <= MAX_Y // Абсолютне обмеження по MAX_Y
-        });
+        let render_distance = self.render_distance;
+        let world_height = self.world_height;
+        self.center_pos = center_pos;
 
-        // Генеруємо чанки в межах render_distance, але між MIN_Y і MAX_Y
-        for x in -self.render_distance..=self.render_distance {
-            for y in (MIN_Y - center_pos.y).max(-self.render_distance)
-                ..=(MAX_Y - center_pos.y).min(self.render_distance)
-            {
-                for z in -self.render_distance..=self.render_distance {
-                    let chunk_pos = center_pos + IVec3::new(x, y, z);
-                    if chunk_pos.y >= MIN_Y
-                        && chunk_pos.y <= MAX_Y
-                        && !self.chunks.contains_key(&chunk_pos)
+        // Видаляємо чанки поза межами render_distance або за межами висоти світу
+        let unloaded: Vec<IVec3> = self
+            .chunks
+            .keys()
+            .filter(|pos| !Self::in_range(render_distance, world_height, **pos, center_pos))
+            .copied()
+            .collect();
+        for chunk_pos in unloaded {
+            let chunk = self.chunks.remove(&chunk_pos).unwrap();
+            if chunk.modified {
+                self.save_chunk(chunk_pos, chunk.voxels);
+            }
+            // Грані й затінення сусідів на цій межі тепер залежать від UnloadedChunkPolicy
+            for offset in neighbor_offsets() {
+                self.mark_dirty(chunk_pos + offset);
+            }
+        }
+
+        // Чанки, що перейшли в інше кільце деталізації, перебудовуються
+        let lod_changed: Vec<IVec3> = self
+            .chunks
+            .values()
+            .filter(|chunk| chunk.lod != self.lod_level(chunk.position))
+            .map(|chunk| chunk.position)
+            .collect();
+        for chunk_pos in lod_changed {
+            let lod = self.lod_level(chunk_pos);
+            let chunk = self.chunks.get_mut(&chunk_pos).unwrap();
+            chunk.lod = lod;
+            chunk.mark_dirty();
+        }
+
+        // Запити на чанки, що вже вийшли за межі, більше не потрібні
+        self.generation
+            .cancel(|pos| Self::in_range(render_distance, world_height, pos, center_pos));
+        self.meshing
+            .cancel(|pos| Self::in_range(render_distance, world_height, pos, center_pos));
+
+        // Черга відсутніх чанків у межах render_distance і висоти світу
+        self.load_queue.clear();
+        for x in -render_distance..=render_distance {
+            for y in world_height.visible_range(center_pos.y, render_distance) {
+                for z in -render_distance..=render_distance {
+                    let chunk_pos = IVec3::new(center_pos.x + x, y, center_pos.z + z);
+                    if !self.chunks.contains_key(&chunk_pos)
+                        && !self.generation.is_pending(chunk_pos)
                     {
-                        let chunk = Chunk::new(&self.shader_program, chunk_pos, self.world_seed, self);
-                        self.chunks.insert(chunk_pos, chunk);
+                        self.load_queue.push(chunk_pos);
                     }
                 }
             }
         }
+        self.sort_load_queue();
+    }
 
+    // Рівень деталізації за відстанню від центру в чанках (по найбільшій з осей)
+    pub fn lod_level(&self, chunk_pos: IVec3) -> u8 {
+        let distance = (chunk_pos - self.center_pos).abs().max_element();
+        self.lod_distances
+            .iter()
+            .filter(|&&lod_distance| distance >= lod_distance)
+            .count() as u8
+    }
 
+    // Менше — важливіше: ближчі чанки першими, а ті, що позаду камери, — до трьох разів пізніше
+    fn priority(&self, chunk_pos: IVec3) -> f32 {
+        let chunk_center = (chunk_pos.as_vec3() + 0.5) * CHUNK_SIZE as f32;
+        let to_chunk = chunk_center - self.player_pos;
+        let distance = to_chunk.length();
+        let facing = to_chunk.normalize_or_zero().dot(self.view_dir);
+        distance * (2.0 - facing)
     }
 
+    // Сортує так, що найважливіший чанк опиняється в кінці
+    fn sort_by_priority(&self, positions: &mut [IVec3]) {
+        positions.sort_by(|a, b| self.priority(*b).total_cmp(&self.priority(*a)));
+    }
+
+    fn sort_load_queue(&mut self) {
+        let mut load_queue = std::mem::take(&mut self.load_queue);
+        self.sort_by_priority(&mut load_queue);
+        self.load_queue = load_queue;
+    }
+
+    fn request_chunks(&mut self, limit: usize) {
+        let mut requested = 0;
+        while requested < limit && self.generation.pending_count() < self.max_generation_jobs {
+            let Some(chunk_pos) = self.load_queue.pop() else {
+                break;
+            };
+            // Ще не дописаний на диск чанк повертається з пам'яті, без генерації
+            if let Some(voxels) = self.unsaved.get(&chunk_pos) {
+                let voxels = PalettedVoxels::clone(voxels);
+                self.insert_chunk(chunk_pos, voxels);
+                continue;
+            }
+            self.generation.request(chunk_pos, ());
+            requested += 1;
+        }
+    }
+
+    fn insert_chunk(&mut self, chunk_pos: IVec3, voxels: PalettedVoxels) {
+        // Центр міг зміститися, поки чанк генерувався
+        if !Self::in_range(
+            self.render_distance,
+            self.world_height,
+            chunk_pos,
+            self.center_pos,
+        ) {
+            return;
+        }
+        let mut chunk = Chunk::new(chunk_pos, voxels, &self.registry);
+        chunk.lod = self.lod_level(chunk_pos);
+        self.chunks.insert(chunk_pos, chunk);
+        for offset in neighbor_offsets() {
+            self.mark_dirty(chunk_pos + offset);
+        }
+    }
+
+    // Раз на кадр, після генерації, щоб нові сусіди вже були в self.chunks.
+    // Меші будуються у фоні зі знімків, тож подальші зміни чанка їх не зачіпають
+    fn build_dirty_meshes(&mut self, limit: usize) {
+        let mut dirty: Vec<IVec3> = Vec::new();
+        let mut skipped: Vec<IVec3> = Vec::new();
+        for chunk in self.chunks.values().filter(|chunk| chunk.is_dirty) {
+            if self.needs_mesh(chunk) {
+                dirty.push(chunk.position);
+            } else {
+                skipped.push(chunk.position);
+            }
+        }
+
+        // Порожній меш одразу: рендерер прибере старий і нічого не малюватиме
+        for chunk_pos in skipped {
+            let chunk = self.chunks.get_mut(&chunk_pos).unwrap();
+            chunk.is_dirty = false;
+            chunk.skipped = true;
+            chunk.mesh = Some(ChunkMesh::default());
+        }
+
+        self.sort_by_priority(&mut dirty);
+        let free_slots = self.max_meshing_jobs.saturating_sub(self.meshing.pending_count());
+        for chunk_pos in dirty.into_iter().rev().take(limit.min(free_slots)) {
+            let Some(neighborhood) = self.neighborhood(chunk_pos) else {
+                continue;
+            };
+            let job = MeshJob {
+                chunks: neighborhood.snapshot(),
+                version: neighborhood.chunk.version,
+                unloaded: self.unloaded_chunks,
+                world_height: self.world_height,
+                lod: neighborhood.chunk.lod,
+            };
+            self.meshing.request(chunk_pos, job);
+            if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
+                chunk.is_dirty = false;
+                chunk.skipped = false;
+            }
+        }
+    }
 
+    // Порожній чанк не має граней; суцільний не має видимих, якщо всі сусіди через грані
+    // теж суцільні (незавантажені рахуються суцільними за UnloadedChunkPolicy::Solid,
+    // а над і під межами світу — повітря)
+    fn needs_mesh(&self, chunk: &Chunk) -> bool {
+        match chunk.fill {
+            ChunkFill::Empty => false,
+            ChunkFill::Surface => true,
+            ChunkFill::Full => FACE_NEIGHBORS.iter().any(|offset| {
+                let neighbor_pos = chunk.position + *offset;
+                match self.chunks.get(&neighbor_pos) {
+                    Some(neighbor) => neighbor.fill != ChunkFill::Full,
+                    None if !self.world_height.contains(neighbor_pos.y) => true,
+                    None => self.unloaded_chunks == UnloadedChunkPolicy::Air,
+                }
+            }),
+        }
+    }
+
+    // Меш приймається, лише якщо чанк не змінювався з моменту створення знімка
+    fn accept_mesh(&mut self, chunk_pos: IVec3, version: u64, mesh: ChunkMesh) {
+        if let Some(chunk) = self.chunks.get_mut(&chunk_pos)
+            && chunk.version == version
+        {
+            chunk.mesh = Some(mesh);
+        }
+    }
 }
@@ -1,85 +1,107 @@
+use crate::blocks::{AIR, BlockId, BlockRegistry};
 use crate::meshes::chunk_mesh::ChunkMesh;
-use crate::settings::{CHUNK_AREA, CHUNK_SIZE, CHUNK_VOL};
-use crate::shader_program::{ShaderProgram, set_uniform_mat4};
-use crate::world::World;
-use gl;
-use glam::{IVec3, Mat4};
-use noise::{NoiseFn, Seedable, Simplex};
-use rand::Rng;
+use crate::settings::{CHUNK_AREA, CHUNK_SIZE};
+use crate::world_objects::PalettedVoxels;
+use glam::IVec3;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Версії беруться з одного лічильника: чанк, вивантажений і завантажений знову,
+// не повторить версію свого попереднього меша
+static NEXT_VERSION: AtomicU64 = AtomicU64::new(1);
+
+fn next_version() -> u64 {
+    NEXT_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
+// Що є в чанку: порожні й закопані чанки не мешаються і не малюються
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkFill {
+    Empty,   // Лише повітря
+    Full,    // Лише непрозорі блоки
+    Surface, // Усе інше
+}
+
+impl ChunkFill {
+    // За палітрою, тож після змін може бути Surface там, де вже Empty чи Full
+    pub fn of(voxels: &PalettedVoxels, registry: &BlockRegistry) -> Self {
+        let palette = voxels.palette();
+        if palette.iter().all(|&voxel_id| voxel_id == AIR) {
+            ChunkFill::Empty
+        } else if palette.iter().all(|&voxel_id| registry.is_opaque(voxel_id)) {
+            ChunkFill::Full
+        } else {
+            ChunkFill::Surface
+        }
+    }
+}
 
 pub struct Chunk {
-    pub voxels: Vec<u8>,
+    // Спільні зі знімками для мешингу; копіюються лише при зміні під час мешингу
+    pub voxels: Arc<PalettedVoxels>,
+    // Меш, який ще не забрав рендерер (див. World::take_meshes)
     pub mesh: Option<ChunkMesh>,
-    pub shader_program: ShaderProgram,
     pub position: IVec3,
-    pub m_model: Mat4,
-    pub world: *const World,
+    // Воксели або сусіди змінилися, меш треба перебудувати
+    pub is_dirty: bool,
+    // Зростає з кожною зміною, що впливає на меш; так відкидаються застарілі меші
+    pub version: u64,
+    // Змінений після генерації чи завантаження, треба записати на диск
+    pub modified: bool,
+    pub fill: ChunkFill,
+    // Останній раз меш не будувався: чанк порожній або закритий з усіх боків
+    pub skipped: bool,
+    // Рівень деталізації меша: воксели зменшуються у 2^lod разів (див. World::lod_level)
+    pub lod: u8,
 }
 
 impl Chunk {
-    pub fn new(
-        shader_program: &ShaderProgram,
-        position: IVec3,
-        seed: u32,
-        world: &World,
-    ) -> Self {
-        let m_model = Mat4::from_translation(position.as_vec3() * CHUNK_SIZE as f32);
-        let voxels = Self::build_voxels(position, seed);
-        let mut chunk = Self {
-            voxels,
+    // Воксели дає WorldGenerator; меш будує World, коли поруч доступні сусідні чанки
+    pub fn new(position: IVec3, voxels: PalettedVoxels, registry: &BlockRegistry) -> Self {
+        Self {
+            fill: ChunkFill::of(&voxels, registry),
+            skipped: false,
+            lod: 0,
+            voxels: Arc::new(voxels),
             mesh: None,
-            shader_program: shader_program.clone(),
             position,
-            m_model,
-            world: world as *const World,
-        };
-        chunk.build_mesh();
-        chunk
+            is_dirty: true,
+            version: next_version(),
+            modified: false,
+        }
     }
 
-    // Генерація вокселів перенесена в World
-    fn build_voxels(position: IVec3, seed: u32) -> Vec<u8> {
-        let mut voxels = vec![0u8; CHUNK_VOL as usize];
-        let simplex = Simplex::new(seed); // Можна передати сід із World, якщо потрібно
-        let (cx, cy, cz) = (position.x, position.y, position.z);
-
-        let rng = rand::thread_rng().gen_range(1..=100);
+    // Легка копія вокселів без меша — для роботи в інших потоках
+    pub fn snapshot(&self) -> Chunk {
+        Self {
+            voxels: self.voxels.clone(),
+            mesh: None,
+            position: self.position,
+            is_dirty: false,
+            version: self.version,
+            modified: self.modified,
+            fill: self.fill,
+            skipped: self.skipped,
+            lod: self.lod,
+        }
+    }
 
-        for x in 0..CHUNK_SIZE {
-            let wx = x as f32 + cx as f32 * CHUNK_SIZE as f32;
-            for z in 0..CHUNK_SIZE {
-                let wz = z as f32 + cz as f32 * CHUNK_SIZE as f32;
-                let noise_value = simplex.get([wx as f64 * 0.01, wz as f64 * 0.01]);
-                let world_height = (noise_value * 32.0 + 32.0) as i32;
-                // Висота поверхні в межах MIN_Y..MAX_Y
-                // let world_height = MIN_Y * CHUNK_SIZE as i32 + ((noise_value + 32.0) * 0.5 * (MAX_Y - MIN_Y) as f64 * CHUNK_SIZE as f64) as i32;
-                let local_height =
-                    (world_height - cy * CHUNK_SIZE as i32).clamp(0, CHUNK_SIZE as i32);
+    pub fn mark_dirty(&mut self) {
+        self.is_dirty = true;
+        self.version = next_version();
+    }
 
-                for y in 0..local_height as u32 {
-                    let wy = y as f32 + cy as f32 * CHUNK_SIZE as f32;
-                    voxels[(x + CHUNK_SIZE * z + CHUNK_AREA * y) as usize] = rng;
-                }
-            }
-        }
-        voxels
+    pub fn voxel_index(local_pos: IVec3) -> usize {
+        (local_pos.x + CHUNK_SIZE as i32 * local_pos.z + CHUNK_AREA as i32 * local_pos.y) as usize
     }
 
-    pub fn build_mesh(&mut self) {
-        self.mesh = Some(ChunkMesh::new(self));
+    pub fn get_voxel(&self, local_pos: IVec3) -> BlockId {
+        self.voxels.get(Self::voxel_index(local_pos))
     }
 
-    pub fn render(&self) {
-        if let Some(mesh) = &self.mesh {
-            unsafe {
-                gl::UseProgram(self.shader_program.chunk_program());
-                set_uniform_mat4(
-                    self.shader_program.chunk_program(),
-                    "m_model",
-                    &self.m_model,
-                );
-            }
-            mesh.render();
-        }
+    pub fn set_voxel(&mut self, local_pos: IVec3, voxel_id: BlockId) {
+        Arc::make_mut(&mut self.voxels).set(Self::voxel_index(local_pos), voxel_id);
+        self.modified = true;
+        self.mark_dirty();
     }
 }
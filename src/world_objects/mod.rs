@@ -1,3 +1,7 @@
 pub mod chunk;
+pub mod neighborhood;
+pub mod palette;
 
-pub use chunk::Chunk;
+pub use chunk::{Chunk, ChunkFill};
+pub use neighborhood::ChunkNeighborhood;
+pub use palette::PalettedVoxels;
@@ -0,0 +1,99 @@
+use crate::blocks::{AIR, BlockId};
+use crate::settings::{CHUNK_SIZE, WorldHeight};
+use crate::world_objects::Chunk;
+use glam::IVec3;
+
+// Зсуви до сусідів через грані, у порядку face_id мешера:
+// верх, низ, право, ліво, зад, перед
+pub const FACE_NEIGHBORS: [IVec3; 6] = [
+    IVec3::Y,
+    IVec3::NEG_Y,
+    IVec3::X,
+    IVec3::NEG_X,
+    IVec3::NEG_Z,
+    IVec3::Z,
+];
+
+// Зсуви до всіх 26 сусідів, зокрема через ребра й кути: затінення кутів
+// на межі чанка читає і діагональних сусідів
+pub fn neighbor_offsets() -> impl Iterator<Item = IVec3> {
+    (0..27)
+        .map(ChunkNeighborhood::index_to_offset)
+        .filter(|offset| *offset != IVec3::ZERO)
+}
+
+// Чанк разом із сусідами 3x3x3 навколо нього, позичені з World.
+// Дозволяє читати воксели через межі чанка без вказівника на World.
+#[derive(Clone, Copy)]
+pub struct ChunkNeighborhood<'a> {
+    pub chunk: &'a Chunk,
+    chunks: [Option<&'a Chunk>; 27],
+    world_height: WorldHeight,
+}
+
+impl<'a> ChunkNeighborhood<'a> {
+    // get_chunk повертає чанк за абсолютною позицією, якщо він завантажений
+    pub fn new(
+        chunk: &'a Chunk,
+        world_height: WorldHeight,
+        get_chunk: impl Fn(IVec3) -> Option<&'a Chunk>,
+    ) -> Self {
+        let mut chunks = [None; 27];
+        for (i, slot) in chunks.iter_mut().enumerate() {
+            let offset = Self::index_to_offset(i);
+            *slot = if offset == IVec3::ZERO {
+                Some(chunk)
+            } else {
+                get_chunk(chunk.position + offset)
+            };
+        }
+        Self {
+            chunk,
+            chunks,
+            world_height,
+        }
+    }
+
+    // Знімки центрального чанка (першим) і всіх завантажених сусідів
+    pub fn snapshot(&self) -> Vec<Chunk> {
+        let mut chunks = vec![self.chunk.snapshot()];
+        chunks.extend(
+            self.chunks
+                .iter()
+                .flatten()
+                .filter(|chunk| chunk.position != self.chunk.position)
+                .map(|chunk| chunk.snapshot()),
+        );
+        chunks
+    }
+
+    // Сусід за зсувом у чанках (кожна компонента в -1..=1)
+    pub fn neighbor(&self, offset: IVec3) -> Option<&'a Chunk> {
+        if offset.abs().max_element() > 1 {
+            return None;
+        }
+        self.chunks[Self::offset_to_index(offset)]
+    }
+
+    // Воксель за локальними координатами центрального чанка; координати можуть
+    // виходити за межі чанка не більше ніж на CHUNK_SIZE.
+    // None — якщо потрібний сусід не завантажений. Над і під межами світу — повітря:
+    // там чанків не буде ніколи, тож UnloadedChunkPolicy до них не застосовується
+    pub fn voxel(&self, local_pos: IVec3) -> Option<BlockId> {
+        let offset = local_pos.div_euclid(IVec3::splat(CHUNK_SIZE as i32));
+        if !self.world_height.contains(self.chunk.position.y + offset.y) {
+            return Some(AIR);
+        }
+        let chunk = self.neighbor(offset)?;
+        Some(chunk.get_voxel(local_pos.rem_euclid(IVec3::splat(CHUNK_SIZE as i32))))
+    }
+
+    fn offset_to_index(offset: IVec3) -> usize {
+        ((offset.x + 1) + (offset.y + 1) * 3 + (offset.z + 1) * 9) as usize
+    }
+
+    fn index_to_offset(index: usize) -> IVec3 {
+        let i = index as i32;
+        IVec3::new(i % 3 - 1, (i / 3) % 3 - 1, i / 9 - 1)
+    }
+}
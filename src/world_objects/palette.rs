@@ -0,0 +1,166 @@
+use crate::blocks::BlockId;
+use crate::settings::CHUNK_VOL;
+
+// Воксели чанка як палітра блоків і упаковані індекси в ній.
+// Порядок вокселів той самий, що й у Chunk::voxel_index: x + CHUNK_SIZE * z + CHUNK_AREA * y
+#[derive(Debug, Clone)]
+pub enum PalettedVoxels {
+    // Увесь чанк — один блок (повітря над рельєфом, камінь під ним)
+    Uniform(BlockId),
+    Packed {
+        palette: Vec<BlockId>,
+        bits: u32, // 1, 2, 4 або 8 — індекси не перетинають межі u64
+        data: Vec<u64>,
+    },
+}
+
+// Найменша ширина індексу для палітри з len блоків
+fn bits_for(len: usize) -> u32 {
+    match len {
+        0..=2 => 1,
+        3..=4 => 2,
+        5..=16 => 4,
+        _ => 8,
+    }
+}
+
+fn words_for(bits: u32) -> usize {
+    (CHUNK_VOL / (u64::BITS / bits)) as usize
+}
+
+impl PalettedVoxels {
+    pub fn from_voxels(voxels: &[BlockId]) -> Self {
+        debug_assert_eq!(voxels.len(), CHUNK_VOL as usize);
+        let mut palette: Vec<BlockId> = Vec::new();
+        let mut lookup = [None; 256];
+        let indices: Vec<usize> = voxels
+            .iter()
+            .map(|&voxel_id| {
+                *lookup[voxel_id as usize].get_or_insert_with(|| {
+                    palette.push(voxel_id);
+                    palette.len() - 1
+                })
+            })
+            .collect();
+        if palette.len() <= 1 {
+            return Self::Uniform(palette.first().copied().unwrap_or_default());
+        }
+
+        let bits = bits_for(palette.len());
+        let mut data = vec![0; words_for(bits)];
+        for (index, &palette_index) in indices.iter().enumerate() {
+            write_index(&mut data, bits, index, palette_index);
+        }
+        Self::Packed {
+            palette,
+            bits,
+            data,
+        }
+    }
+
+    pub fn get(&self, index: usize) -> BlockId {
+        match self {
+            Self::Uniform(voxel_id) => *voxel_id,
+            Self::Packed {
+                palette,
+                bits,
+                data,
+            } => palette[read_index(data, *bits, index)],
+        }
+    }
+
+    pub fn set(&mut self, index: usize, voxel_id: BlockId) {
+        if let Self::Uniform(current) = *self {
+            if current == voxel_id {
+                return;
+            }
+            *self = Self::Packed {
+                palette: vec![current],
+                bits: 1,
+                data: vec![0; words_for(1)],
+            };
+        }
+        let Self::Packed {
+            palette,
+            bits,
+            data,
+        } = self
+        else {
+            unreachable!()
+        };
+
+        let palette_index = match palette.iter().position(|&id| id == voxel_id) {
+            Some(palette_index) => palette_index,
+            None => {
+                // Палітра заповнена: розширюємо індекси. Невживані записи не прибираються
+                if palette.len() == 1 << *bits {
+                    let new_bits = bits_for(palette.len() + 1);
+                    let mut new_data = vec![0; words_for(new_bits)];
+                    for i in 0..CHUNK_VOL as usize {
+                        write_index(&mut new_data, new_bits, i, read_index(data, *bits, i));
+                    }
+                    *bits = new_bits;
+                    *data = new_data;
+                }
+                palette.push(voxel_id);
+                palette.len() - 1
+            }
+        };
+        write_index(data, *bits, index, palette_index);
+    }
+
+    // Усі блоки, що можуть траплятися в чанку (після змін — з надлишком)
+    pub fn palette(&self) -> &[BlockId] {
+        match self {
+            Self::Uniform(voxel_id) => std::slice::from_ref(voxel_id),
+            Self::Packed { palette, .. } => palette,
+        }
+    }
+
+    // Блок, яким заповнено весь чанк
+    pub fn uniform(&self) -> Option<BlockId> {
+        match self {
+            Self::Uniform(voxel_id) => Some(*voxel_id),
+            Self::Packed { .. } => None,
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<BlockId> {
+        (0..CHUNK_VOL as usize).map(|index| self.get(index)).collect()
+    }
+
+    // Приблизний розмір у пам'яті, байти
+    pub fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + match self {
+                Self::Uniform(_) => 0,
+                Self::Packed { palette, data, .. } => palette.len() + data.len() * 8,
+            }
+    }
+}
+
+// Порівнюються воксели, а не представлення
+impl PartialEq for PalettedVoxels {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Uniform(a), Self::Uniform(b)) => a == b,
+            _ => (0..CHUNK_VOL as usize).all(|index| self.get(index) == other.get(index)),
+        }
+    }
+}
+
+impl Eq for PalettedVoxels {}
+
+fn read_index(data: &[u64], bits: u32, index: usize) -> usize {
+    let per_word = (u64::BITS / bits) as usize;
+    let shift = (index % per_word) as u32 * bits;
+    ((data[index / per_word] >> shift) & ((1 << bits) - 1)) as usize
+}
+
+fn write_index(data: &mut [u64], bits: u32, index: usize, palette_index: usize) {
+    let per_word = (u64::BITS / bits) as usize;
+    let shift = (index % per_word) as u32 * bits;
+    let mask = ((1u64 << bits) - 1) << shift;
+    let word = &mut data[index / per_word];
+    *word = (*word & !mask) | ((palette_index as u64) << shift);
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+use voxel_engine::blocks::loader::parse_blocks;
+use voxel_engine::blocks::{BlockFileError, BlockRegistry, load_blocks};
+
+fn parse_errors(source: &str) -> Vec<BlockFileError> {
+    parse_blocks(source, Path::new("blocks.toml"), |name| name != "missing")
+        .expect_err("source must be rejected")
+}
+
+// Повідомлення разом із позицією у вигляді path:line:col: msg
+fn messages(source: &str) -> Vec<String> {
+    parse_errors(source)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn builtin_registry_matches_blocks_file() {
+    let from_file = load_blocks(Path::new("src/assets/blocks.toml")).expect("blocks.toml is valid");
+    let builtin = BlockRegistry::default();
+    assert_eq!(builtin.texture_names(), from_file.texture_names());
+    let names = |registry: &BlockRegistry| {
+        registry
+            .iter()
+            .map(|block| (block.id, block.name.clone(), block.textures))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&builtin), names(&from_file));
+    assert_eq!(builtin.colors(), from_file.colors());
+}
+
+#[test]
+fn duplicate_id_is_reported_at_second_id() {
+    let source = "\
+[[block]]
+id = 1
+name = \"stone\"
+textures = { all = \"frame\" }
+
+[[block]]
+id = 1
+name = \"dirt\"
+textures = { all = \"frame\" }
+";
+    assert_eq!(
+        messages(source),
+        ["blocks.toml:7:6: duplicate block id 1 (already used by \"stone\")"]
+    );
+}
+
+#[test]
+fn duplicate_name_is_reported_at_second_name() {
+    let source = "\
+[[block]]
+id = 1
+name = \"stone\"
+textures = { all = \"frame\" }
+
+[[block]]
+id = 2
+name = \"stone\"
+textures = { all = \"frame\" }
+";
+    assert_eq!(
+        messages(source),
+        ["blocks.toml:8:8: duplicate block name \"stone\""]
+    );
+}
+
+#[test]
+fn unknown_field_is_reported_with_position() {
+    let source = "\
+[[block]]
+id = 1
+name = \"stone\"
+textures = { all = \"frame\" }
+hardnes = 1.5
+";
+    let errors = parse_errors(source);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].position, Some((5, 1)));
+    assert!(
+        errors[0].message.contains("unknown field `hardnes`"),
+        "{}",
+        errors[0].message
+    );
+}
+
+#[test]
+fn missing_texture_is_reported_at_its_name() {
+    let source = "\
+[[block]]
+id = 1
+name = \"stone\"
+textures = { top = \"frame\", side = \"missing\", bottom = \"frame\" }
+";
+    assert_eq!(
+        messages(source),
+        ["blocks.toml:4:36: missing texture \"missing\""]
+    );
+}
+
+#[test]
+fn faces_without_texture_are_listed() {
+    let source = "\
+[[block]]
+id = 1
+name = \"stone\"
+textures = { top = \"frame\" }
+";
+    assert_eq!(
+        messages(source),
+        ["blocks.toml:4:12: block \"stone\" has no texture for bottom, right, left, back, front"]
+    );
+}
+
+#[test]
+fn all_errors_are_collected() {
+    let source = "\
+[[block]]
+id = 0
+name = \"air\"
+textures = { all = \"frame\" }
+light_emission = 16
+";
+    assert_eq!(
+        messages(source),
+        [
+            "blocks.toml:2:6: id 0 is reserved for air",
+            "blocks.toml:3:8: duplicate block name \"air\"",
+            "blocks.toml:5:18: light_emission must be in 0..=15",
+        ]
+    );
+}
@@ -0,0 +1,507 @@
+use glam::IVec3;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashMap;
+use voxel_engine::blocks::{AIR, BlockId, BlockRegistry, MAX_TEXTURES, MODEL_UNITS};
+use voxel_engine::generators::{NoiseGenerator, WorldGenerator};
+use voxel_engine::meshes::chunk_mesh::{ChunkMesh, ChunkVertex, MeshData};
+use voxel_engine::settings::{CHUNK_SIZE, CHUNK_VOL, MesherKind, UnloadedChunkPolicy, WorldHeight};
+use voxel_engine::world_objects::{Chunk, ChunkNeighborhood, PalettedVoxels};
+
+const SEED: u32 = 1234;
+
+// Квадрати меша в сталому порядку: мешери обходять грані по-різному
+fn quads(mesh: &MeshData) -> Vec<Vec<u32>> {
+    let quad_size = 4 * ChunkMesh::FORMAT_SIZE as usize;
+    let mut quads: Vec<Vec<u32>> = mesh
+        .vertex_data
+        .chunks(quad_size)
+        .map(<[u32]>::to_vec)
+        .collect();
+    quads.sort();
+    quads
+}
+
+fn assert_same_geometry(chunks: &HashMap<IVec3, Chunk>, registry: &BlockRegistry) {
+    let center = &chunks[&IVec3::ZERO];
+    let neighborhood = ChunkNeighborhood::new(center, WorldHeight::Cubic, |pos| chunks.get(&pos));
+    for unloaded in [UnloadedChunkPolicy::Solid, UnloadedChunkPolicy::Air] {
+        let reference = ChunkMesh::new(&neighborhood, registry, unloaded, MesherKind::PerFace, 0);
+        let bitmask = ChunkMesh::new(&neighborhood, registry, unloaded, MesherKind::Bitmask, 0);
+        assert_eq!(quads(&bitmask.opaque), quads(&reference.opaque), "{unloaded:?}");
+        assert_eq!(
+            quads(&bitmask.translucent),
+            quads(&reference.translucent),
+            "{unloaded:?}"
+        );
+    }
+}
+
+// Сумарна площа квадратів кожної грані кожного блока, у квадратних частках блока
+fn face_areas(mesh: &MeshData) -> HashMap<(u8, u16), i64> {
+    let vertices: Vec<ChunkVertex> = mesh.vertices().collect();
+    let mut areas = HashMap::new();
+    for quad in vertices.chunks(4) {
+        let (face_id, voxel_id) = (quad[0].face_id, quad[0].voxel_id);
+        // Нормаль: верх і низ — y, право й ліво — x, зад і перед — z
+        let normal = [1, 0, 2][face_id as usize / 2];
+        let area: i64 = (0..3)
+            .filter(|&axis| axis != normal)
+            .map(|axis| {
+                let coords = quad.iter().map(|vertex| vertex.position[axis] as i64);
+                coords.clone().max().unwrap() - coords.min().unwrap()
+            })
+            .product();
+        *areas.entry((face_id, voxel_id)).or_insert(0) += area;
+    }
+    areas
+}
+
+// Злиті грані мають покривати рівно ту саму площу, що й окремі
+fn assert_same_area(chunks: &HashMap<IVec3, Chunk>, registry: &BlockRegistry) {
+    let center = &chunks[&IVec3::ZERO];
+    let neighborhood = ChunkNeighborhood::new(center, WorldHeight::Cubic, |pos| chunks.get(&pos));
+    for unloaded in [UnloadedChunkPolicy::Solid, UnloadedChunkPolicy::Air] {
+        let reference = ChunkMesh::new(&neighborhood, registry, unloaded, MesherKind::PerFace, 0);
+        let greedy = ChunkMesh::new(&neighborhood, registry, unloaded, MesherKind::Greedy, 0);
+        assert_eq!(
+            face_areas(&greedy.opaque),
+            face_areas(&reference.opaque),
+            "{unloaded:?}"
+        );
+        assert_eq!(
+            face_areas(&greedy.translucent),
+            face_areas(&reference.translucent),
+            "{unloaded:?}"
+        );
+        assert!(greedy.opaque.quad_count() <= reference.opaque.quad_count());
+    }
+}
+
+// Половина вокселів — повітря, решта — будь-які блоки з реєстру, разом з
+// прозорими й неповними моделями
+fn random_voxels(rng: &mut ChaCha8Rng, registry: &BlockRegistry) -> PalettedVoxels {
+    let blocks: Vec<BlockId> = registry
+        .iter()
+        .map(|block| block.id)
+        .filter(|&id| id != AIR)
+        .collect();
+    let voxels: Vec<BlockId> = (0..CHUNK_VOL)
+        .map(|_| {
+            if rng.random_bool(0.5) {
+                AIR
+            } else {
+                blocks[rng.random_range(0..blocks.len())]
+            }
+        })
+        .collect();
+    PalettedVoxels::from_voxels(&voxels)
+}
+
+// Випадкові чанки 3x3x3; частина сусідів не завантажена, щоб перевірити обидві
+// UnloadedChunkPolicy
+fn random_chunks(registry: &BlockRegistry) -> Vec<HashMap<IVec3, Chunk>> {
+    let mut rng = ChaCha8Rng::seed_from_u64(SEED as u64);
+    (0..2)
+        .map(|_| {
+            let mut chunks = HashMap::new();
+            for x in -1..=1 {
+                for y in -1..=1 {
+                    for z in -1..=1 {
+                        let position = IVec3::new(x, y, z);
+                        if position == IVec3::ZERO || rng.random_bool(0.7) {
+                            let voxels = random_voxels(&mut rng, registry);
+                            chunks.insert(position, Chunk::new(position, voxels, registry));
+                        }
+                    }
+                }
+            }
+            chunks
+        })
+        .collect()
+}
+
+// Рельєф з NoiseGenerator під поверхнею, на ній і над нею
+fn terrain_chunks(registry: &BlockRegistry) -> Vec<HashMap<IVec3, Chunk>> {
+    let generator = NoiseGenerator::new(SEED, registry);
+    [-1, 0, 1]
+        .into_iter()
+        .map(|center_y| {
+            let mut chunks = HashMap::new();
+            for x in -1..=1 {
+                for y in -1..=1 {
+                    for z in -1..=1 {
+                        // Воксели з висоти center_y, але центральний чанк лежить у нулі
+                        let position = IVec3::new(x, center_y + y, z);
+                        let voxels = PalettedVoxels::from_voxels(&generator.generate(position));
+                        chunks.insert(
+                            IVec3::new(x, y, z),
+                            Chunk::new(IVec3::new(x, y, z), voxels, registry),
+                        );
+                    }
+                }
+            }
+            chunks
+        })
+        .collect()
+}
+
+fn uniform_chunk(registry: &BlockRegistry) -> HashMap<IVec3, Chunk> {
+    let stone = registry.id("stone").unwrap();
+    let chunk = Chunk::new(IVec3::ZERO, PalettedVoxels::Uniform(stone), registry);
+    HashMap::from([(IVec3::ZERO, chunk)])
+}
+
+#[test]
+fn bitmask_mesher_matches_reference_on_random_chunks() {
+    let registry = BlockRegistry::default();
+    for chunks in random_chunks(&registry) {
+        assert_same_geometry(&chunks, &registry);
+    }
+}
+
+#[test]
+fn bitmask_mesher_matches_reference_on_terrain() {
+    let registry = BlockRegistry::default();
+    for chunks in terrain_chunks(&registry) {
+        assert_same_geometry(&chunks, &registry);
+    }
+}
+
+#[test]
+fn bitmask_mesher_matches_reference_on_uniform_chunk() {
+    let registry = BlockRegistry::default();
+    assert_same_geometry(&uniform_chunk(&registry), &registry);
+}
+
+#[test]
+fn greedy_mesher_covers_reference_faces_on_random_chunks() {
+    let registry = BlockRegistry::default();
+    for chunks in random_chunks(&registry) {
+        assert_same_area(&chunks, &registry);
+    }
+}
+
+#[test]
+fn greedy_mesher_covers_reference_faces_on_terrain() {
+    let registry = BlockRegistry::default();
+    for chunks in terrain_chunks(&registry) {
+        assert_same_area(&chunks, &registry);
+    }
+}
+
+#[test]
+fn greedy_mesher_covers_reference_faces_on_uniform_chunk() {
+    let registry = BlockRegistry::default();
+    assert_same_area(&uniform_chunk(&registry), &registry);
+}
+
+// Кам'яний чанк, у якого завантажений лише сусід з боку +X
+fn border_quads(neighbor: BlockId, unloaded: UnloadedChunkPolicy) -> [usize; 6] {
+    let registry = BlockRegistry::default();
+    let stone = registry.id("stone").unwrap();
+    let chunks = HashMap::from([
+        (
+            IVec3::ZERO,
+            Chunk::new(IVec3::ZERO, PalettedVoxels::Uniform(stone), &registry),
+        ),
+        (
+            IVec3::X,
+            Chunk::new(IVec3::X, PalettedVoxels::Uniform(neighbor), &registry),
+        ),
+    ]);
+    let neighborhood = ChunkNeighborhood::new(&chunks[&IVec3::ZERO], WorldHeight::Cubic, |pos| {
+        chunks.get(&pos)
+    });
+    let mesh = ChunkMesh::new(&neighborhood, &registry, unloaded, MesherKind::PerFace, 0);
+    let mut quads = [0; 6];
+    for vertex in mesh.opaque.vertices().step_by(4) {
+        quads[vertex.face_id as usize] += 1;
+    }
+    quads
+}
+
+#[test]
+fn border_faces_are_culled_against_loaded_neighbors() {
+    let stone = BlockRegistry::default().id("stone").unwrap();
+    let side = CHUNK_SIZE as usize * CHUNK_SIZE as usize;
+    // Грані на межі з каменем сховані за будь-якої політики, з повітрям — видимі
+    assert_eq!(border_quads(stone, UnloadedChunkPolicy::Solid), [0; 6]);
+    assert_eq!(
+        border_quads(AIR, UnloadedChunkPolicy::Solid),
+        [0, 0, side, 0, 0, 0]
+    );
+    assert_eq!(
+        border_quads(stone, UnloadedChunkPolicy::Air),
+        [side, side, 0, side, side, side]
+    );
+    assert_eq!(border_quads(AIR, UnloadedChunkPolicy::Air), [side; 6]);
+}
+
+// Рівнина: нижня половина центрального ряду чанків — камінь, під ним суцільний камінь
+fn flat_terrain(registry: &BlockRegistry) -> HashMap<IVec3, Chunk> {
+    let stone = registry.id("stone").unwrap();
+    let half = CHUNK_VOL as usize / 2; // Воксели йдуть шарами по y знизу вгору
+    let mut layer = vec![AIR; CHUNK_VOL as usize];
+    layer[..half].fill(stone);
+    let mut chunks = HashMap::new();
+    for x in -1..=1 {
+        for y in -1..=1 {
+            for z in -1..=1 {
+                let position = IVec3::new(x, y, z);
+                let voxels = match y {
+                    -1 => PalettedVoxels::Uniform(stone),
+                    0 => PalettedVoxels::from_voxels(&layer),
+                    _ => PalettedVoxels::Uniform(AIR),
+                };
+                chunks.insert(position, Chunk::new(position, voxels, registry));
+            }
+        }
+    }
+    chunks
+}
+
+#[test]
+fn lod_mesh_merges_flat_terrain() {
+    let registry = BlockRegistry::default();
+    let chunks = flat_terrain(&registry);
+    let neighborhood = ChunkNeighborhood::new(&chunks[&IVec3::ZERO], WorldHeight::Cubic, |pos| {
+        chunks.get(&pos)
+    });
+    for lod in 1..=3 {
+        let mesh = ChunkMesh::new(
+            &neighborhood,
+            &registry,
+            UnloadedChunkPolicy::Solid,
+            MesherKind::Greedy,
+            lod,
+        );
+        // Верх і по одній смузі спідниці з кожного боку
+        assert_eq!(mesh.opaque.quad_count(), 5, "lod {lod}");
+        assert!(mesh.translucent.is_empty());
+    }
+}
+
+#[test]
+fn chunk_vertex_round_trips_at_field_limits() {
+    let zero = ChunkVertex {
+        position: [0; 3],
+        voxel_id: 0,
+        face_id: 0,
+        ao: 0,
+        light: 0,
+        texture: 0,
+    };
+    let max = ChunkVertex {
+        position: [1023; 3],
+        voxel_id: u16::MAX,
+        face_id: 7,
+        ao: 3,
+        light: ChunkVertex::MAX_LIGHT,
+        texture: MAX_TEXTURES as u16 - 1,
+    };
+    // Кожне поле на межі окремо, щоб помітити перекриття сусідніх бітових полів
+    let with = |set: fn(&mut ChunkVertex)| {
+        let mut vertex = zero;
+        set(&mut vertex);
+        vertex
+    };
+    let single_fields = [
+        with(|vertex| vertex.position[0] = 1023),
+        with(|vertex| vertex.position[1] = 1023),
+        with(|vertex| vertex.position[2] = 1023),
+        with(|vertex| vertex.voxel_id = u16::MAX),
+        with(|vertex| vertex.face_id = 7),
+        with(|vertex| vertex.ao = 3),
+        with(|vertex| vertex.light = ChunkVertex::MAX_LIGHT),
+        with(|vertex| vertex.texture = MAX_TEXTURES as u16 - 1),
+    ];
+    let chunk_corner = ChunkVertex {
+        position: [CHUNK_SIZE as u16 * MODEL_UNITS as u16; 3],
+        voxel_id: 12,
+        face_id: 5,
+        ao: 1,
+        light: 9,
+        texture: 42,
+    };
+    for vertex in [zero, max, chunk_corner].into_iter().chain(single_fields) {
+        assert_eq!(ChunkVertex::unpack(vertex.pack()), vertex);
+    }
+    assert_eq!(max.pack(), [u32::MAX >> 2, u32::MAX]);
+}
+
+// Окремі блоки посеред порожнього чанка без сусідів
+fn mesh_blocks(registry: &BlockRegistry, blocks: &[(IVec3, &str)]) -> ChunkMesh {
+    let mut chunk = Chunk::new(IVec3::ZERO, PalettedVoxels::Uniform(AIR), registry);
+    for (pos, name) in blocks {
+        chunk.set_voxel(*pos, registry.id(name).unwrap());
+    }
+    let neighborhood = ChunkNeighborhood::new(&chunk, WorldHeight::Cubic, |_| None);
+    ChunkMesh::new(
+        &neighborhood,
+        registry,
+        UnloadedChunkPolicy::Air,
+        MesherKind::PerFace,
+        0,
+    )
+}
+
+// Кількість квадратів блока name по face_id
+fn face_counts(mesh: &MeshData, registry: &BlockRegistry, name: &str) -> [usize; 6] {
+    let voxel_id = registry.id(name).unwrap() as u16;
+    let mut counts = [0; 6];
+    for vertex in mesh.vertices().step_by(4) {
+        if vertex.voxel_id == voxel_id {
+            counts[vertex.face_id as usize] += 1;
+        }
+    }
+    counts
+}
+
+#[test]
+fn same_transparent_blocks_hide_shared_faces() {
+    let registry = BlockRegistry::default();
+    let (left, right) = (IVec3::splat(5), IVec3::new(6, 5, 5));
+    let mesh = mesh_blocks(&registry, &[(left, "glass"), (right, "glass")]);
+    assert!(mesh.opaque.is_empty());
+    // Між двома блоками скла стінки немає, решта граней обох блоків на місці
+    assert_eq!(
+        face_counts(&mesh.translucent, &registry, "glass"),
+        [2, 2, 1, 1, 2, 2]
+    );
+
+    let mesh = mesh_blocks(&registry, &[(left, "glass"), (right, "water")]);
+    assert_eq!(
+        face_counts(&mesh.translucent, &registry, "glass"),
+        [1; 6],
+        "different transparent blocks keep their faces"
+    );
+    assert_eq!(face_counts(&mesh.translucent, &registry, "water"), [1; 6]);
+}
+
+#[test]
+fn transparent_neighbor_does_not_hide_opaque_face() {
+    let registry = BlockRegistry::default();
+    let (left, right) = (IVec3::splat(5), IVec3::new(6, 5, 5));
+    for neighbor in ["water", "leaves"] {
+        let mesh = mesh_blocks(&registry, &[(left, "stone"), (right, neighbor)]);
+        assert_eq!(
+            face_counts(&mesh.opaque, &registry, "stone"),
+            [1; 6],
+            "{neighbor}"
+        );
+        // Сам прозорий блок свою грань біля каменю ховає
+        let neighbor_faces = if neighbor == "water" {
+            face_counts(&mesh.translucent, &registry, neighbor)
+        } else {
+            face_counts(&mesh.opaque, &registry, neighbor)
+        };
+        assert_eq!(neighbor_faces, [1, 1, 1, 0, 1, 1], "{neighbor}");
+    }
+}
+
+#[test]
+fn blocks_go_to_their_render_layer_buffer() {
+    let registry = BlockRegistry::default();
+    let pos = IVec3::splat(5);
+    // Cutout малюється в непрозорому проході, translucent — в окремому
+    for (name, translucent) in [
+        ("stone", false),
+        ("leaves", false),
+        ("tall_grass", false),
+        ("water", true),
+        ("glass", true),
+    ] {
+        let mesh = mesh_blocks(&registry, &[(pos, name)]);
+        let (drawn, empty) = if translucent {
+            (&mesh.translucent, &mesh.opaque)
+        } else {
+            (&mesh.opaque, &mesh.translucent)
+        };
+        assert!(!drawn.is_empty(), "{name}");
+        assert!(empty.is_empty(), "{name}");
+    }
+
+    // Листя поруч із листям: спільні грані сховані, як у скла
+    let mesh = mesh_blocks(
+        &registry,
+        &[(pos, "leaves"), (IVec3::new(5, 6, 5), "leaves")],
+    );
+    assert_eq!(
+        face_counts(&mesh.opaque, &registry, "leaves"),
+        [1, 1, 2, 2, 2, 2]
+    );
+}
+
+fn quad_count(registry: &BlockRegistry, name: &str) -> usize {
+    let mesh = mesh_blocks(registry, &[(IVec3::splat(5), name)]);
+    mesh.opaque.quad_count() + mesh.translucent.quad_count()
+}
+
+#[test]
+fn block_models_emit_their_geometry() {
+    let registry = BlockRegistry::default();
+    assert_eq!(quad_count(&registry, "stone"), 6);
+    assert_eq!(quad_count(&registry, "stone_slab"), 6);
+    // Нижня половина і верхня чверть без нижньої грані, схованої під першою
+    assert_eq!(quad_count(&registry, "stone_stairs"), 11);
+    // Дві діагональні площини, кожна з обох боків
+    assert_eq!(quad_count(&registry, "tall_grass"), 4);
+    assert_eq!(quad_count(&registry, "glass_pane"), 6);
+}
+
+#[test]
+fn only_covered_sides_hide_neighbor_faces() {
+    let registry = BlockRegistry::default();
+    let slab = IVec3::splat(5);
+
+    // Бокова грань плити не закриває камінь поруч і над нею
+    for stone in [IVec3::new(6, 5, 5), IVec3::new(5, 6, 5)] {
+        let mesh = mesh_blocks(&registry, &[(slab, "stone_slab"), (stone, "stone")]);
+        assert_eq!(
+            face_counts(&mesh.opaque, &registry, "stone"),
+            [1; 6],
+            "{stone}"
+        );
+    }
+
+    // Низ плити повний: грань каменю під нею і сам низ плити сховані
+    let mesh = mesh_blocks(
+        &registry,
+        &[(slab, "stone_slab"), (IVec3::new(5, 4, 5), "stone")],
+    );
+    assert_eq!(
+        face_counts(&mesh.opaque, &registry, "stone"),
+        [0, 1, 1, 1, 1, 1]
+    );
+    assert_eq!(
+        face_counts(&mesh.opaque, &registry, "stone_slab"),
+        [1, 0, 1, 1, 1, 1]
+    );
+
+    // У сходинки повний бік +Z, а бік -Z закритий лише наполовину: зникає тільки
+    // задня грань каменю з боку +Z
+    let stairs = IVec3::splat(5);
+    let mesh = mesh_blocks(
+        &registry,
+        &[
+            (stairs, "stone_stairs"),
+            (IVec3::new(5, 5, 6), "stone"),
+            (IVec3::new(5, 5, 4), "stone"),
+        ],
+    );
+    assert_eq!(
+        face_counts(&mesh.opaque, &registry, "stone"),
+        [2, 2, 2, 2, 1, 2]
+    );
+
+    // Панель і рослина не закривають сусіднього каменю з жодного боку
+    for model in ["glass_pane", "tall_grass"] {
+        let mesh = mesh_blocks(&registry, &[(slab, model), (IVec3::new(5, 5, 6), "stone")]);
+        assert_eq!(
+            face_counts(&mesh.opaque, &registry, "stone"),
+            [1; 6],
+            "{model}"
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use voxel_engine::blocks::BlockId;
+use voxel_engine::settings::CHUNK_VOL;
+use voxel_engine::world_objects::PalettedVoxels;
+
+const VOL: usize = CHUNK_VOL as usize;
+
+fn bits(voxels: &PalettedVoxels) -> Option<u32> {
+    match voxels {
+        PalettedVoxels::Uniform(_) => None,
+        PalettedVoxels::Packed { bits, .. } => Some(*bits),
+    }
+}
+
+// Після кожної зміни палітра має збігатися з простим масивом
+fn assert_matches(voxels: &PalettedVoxels, expected: &[BlockId]) {
+    assert_eq!(voxels.to_vec(), expected);
+    for (index, &voxel_id) in expected.iter().enumerate().step_by(97) {
+        assert_eq!(voxels.get(index), voxel_id, "voxel {}", index);
+    }
+}
+
+#[test]
+fn from_voxels_matches_flat_array() {
+    let mut rng = ChaCha8Rng::seed_from_u64(1);
+    for kinds in [1, 2, 3, 4, 5, 16, 17, 200] {
+        let flat: Vec<BlockId> = (0..VOL)
+            .map(|_| rng.random_range(0..kinds) as BlockId)
+            .collect();
+        let voxels = PalettedVoxels::from_voxels(&flat);
+        assert_matches(&voxels, &flat);
+    }
+    assert_eq!(
+        PalettedVoxels::from_voxels(&[7; VOL]),
+        PalettedVoxels::Uniform(7)
+    );
+}
+
+#[test]
+fn uniform_becomes_packed_on_first_different_voxel() {
+    let mut flat = vec![3; VOL];
+    let mut voxels = PalettedVoxels::from_voxels(&flat);
+    assert_eq!(voxels.uniform(), Some(3));
+
+    // Той самий блок нічого не змінює
+    voxels.set(10, 3);
+    assert_eq!(voxels.uniform(), Some(3));
+
+    voxels.set(10, 5);
+    flat[10] = 5;
+    assert_eq!(voxels.uniform(), None);
+    assert_eq!(bits(&voxels), Some(1));
+    assert_eq!(voxels.palette(), [3, 5]);
+    assert_matches(&voxels, &flat);
+}
+
+#[test]
+fn index_width_grows_with_palette() {
+    let mut flat = vec![0; VOL];
+    let mut voxels = PalettedVoxels::from_voxels(&flat);
+    let mut widths = Vec::new();
+    // Кожен новий блок пишемо в інше місце, щоб старі індекси мали пережити перепакування
+    for voxel_id in 1..=40u8 {
+        let index = voxel_id as usize * 811 % VOL;
+        voxels.set(index, voxel_id);
+        flat[index] = voxel_id;
+        assert_matches(&voxels, &flat);
+        if widths.last() != bits(&voxels).as_ref() {
+            widths.push(bits(&voxels).unwrap());
+        }
+    }
+    assert_eq!(widths, [1, 2, 4, 8]);
+}
+
+#[test]
+fn random_edits_match_flat_array() {
+    let mut rng = ChaCha8Rng::seed_from_u64(2);
+    let mut flat = vec![0; VOL];
+    let mut voxels = PalettedVoxels::from_voxels(&flat);
+    for _ in 0..5000 {
+        let index = rng.random_range(0..VOL);
+        let voxel_id = rng.random_range(0..24);
+        voxels.set(index, voxel_id);
+        flat[index] = voxel_id;
+    }
+    assert_matches(&voxels, &flat);
+    assert_eq!(voxels, PalettedVoxels::from_voxels(&flat));
+}
+
+#[test]
+fn equality_ignores_representation() {
+    let uniform = PalettedVoxels::Uniform(4);
+    // Упакований чанк, де всі воксели знову той самий блок
+    let mut packed = PalettedVoxels::from_voxels(&[4; VOL]);
+    packed.set(0, 9);
+    packed.set(0, 4);
+    assert!(packed.uniform().is_none());
+    assert_eq!(packed, uniform);
+    assert_eq!(uniform, packed);
+
+    // Однакові воксели з палітрами в різному порядку
+    let mut first = PalettedVoxels::from_voxels(&[1; VOL]);
+    first.set(5, 2);
+    let mut flat = vec![2; VOL];
+    flat[5] = 1;
+    let mut second = PalettedVoxels::from_voxels(&flat);
+    assert_ne!(first, second);
+    for index in 0..VOL {
+        second.set(index, if index == 5 { 2 } else { 1 });
+    }
+    assert_eq!(first, second);
+    assert_ne!(first, uniform);
+    assert_ne!(PalettedVoxels::Uniform(1), PalettedVoxels::Uniform(2));
+}
@@ -0,0 +1,216 @@
+use glam::IVec3;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use voxel_engine::blocks::BlockId;
+use voxel_engine::settings::{CHUNK_VOL, GeneratorKind, Settings};
+use voxel_engine::storage::{Level, RegionFile, load_level, save_level};
+
+// Окремий каталог для кожного тесту, бо тести йдуть паралельно
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("voxel-storage-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn random_voxels(seed: u64) -> Vec<BlockId> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    (0..CHUNK_VOL).map(|_| rng.random_range(0..8)).collect()
+}
+
+// Запис таблиці для чанка (0, 0, 0) лежить одразу після магії й версії
+fn read_entry(path: &PathBuf) -> (u32, u32) {
+    let bytes = std::fs::read(path).unwrap();
+    let word = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+    (word(8), word(12))
+}
+
+fn write_entry(path: &PathBuf, sector: u32, length: u32) {
+    let mut file = OpenOptions::new().write(true).open(path).unwrap();
+    file.seek(SeekFrom::Start(8)).unwrap();
+    file.write_all(&sector.to_le_bytes()).unwrap();
+    file.write_all(&length.to_le_bytes()).unwrap();
+}
+
+#[test]
+fn chunks_survive_reopening() {
+    let path = temp_dir("round-trip").join("r.0.0.0.vxr");
+    let stone = vec![1; CHUNK_VOL as usize];
+    let noisy = random_voxels(7);
+    {
+        let mut region = RegionFile::open(&path).unwrap();
+        region.write_chunk(IVec3::ZERO, &stone).unwrap();
+        region.write_chunk(IVec3::new(3, 1, 5), &noisy).unwrap();
+        // Довший запис займає більше секторів, ніж попередня версія
+        region.write_chunk(IVec3::ZERO, &random_voxels(8)).unwrap();
+        region.write_chunk(IVec3::ZERO, &stone).unwrap();
+        region.flush().unwrap();
+    }
+
+    let mut region = RegionFile::open(&path).unwrap();
+    assert_eq!(region.read_chunk(IVec3::ZERO).unwrap(), Some(stone));
+    assert_eq!(region.read_chunk(IVec3::new(3, 1, 5)).unwrap(), Some(noisy));
+    assert_eq!(region.read_chunk(IVec3::new(1, 0, 0)).unwrap(), None);
+}
+
+#[test]
+fn corrupt_record_fails_checksum() {
+    let path = temp_dir("checksum").join("r.0.0.0.vxr");
+    let mut region = RegionFile::open(&path).unwrap();
+    region.write_chunk(IVec3::ZERO, &random_voxels(1)).unwrap();
+    drop(region);
+
+    // Перший байт стиснених даних, одразу за CRC32 запису
+    let (sector, _) = read_entry(&path);
+    let mut file = OpenOptions::new().write(true).open(&path).unwrap();
+    file.seek(SeekFrom::Start(sector as u64 * 4096 + 4))
+        .unwrap();
+    file.write_all(&[0xFF]).unwrap();
+    drop(file);
+
+    let mut region = RegionFile::open(&path).unwrap();
+    let err = region.read_chunk(IVec3::ZERO).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().contains("checksum mismatch"), "{}", err);
+}
+
+#[test]
+fn corrupt_entries_are_cleared_on_open() {
+    let dir = temp_dir("entries");
+    let voxels = random_voxels(2);
+    // None — лишити справжнє значення з таблиці
+    for (name, bad_sector, bad_length) in [
+        ("huge-sector", Some(u32::MAX - 1), None),
+        ("in-header", Some(1), None),
+        ("past-end", None, Some(60_000)),
+        ("too-long", None, Some(u32::MAX)),
+        ("too-short", None, Some(2)),
+    ] {
+        let path = dir.join(format!("{}.vxr", name));
+        let mut region = RegionFile::open(&path).unwrap();
+        region
+            .write_chunk(IVec3::ZERO, &[1; CHUNK_VOL as usize])
+            .unwrap();
+        drop(region);
+        let (sector, length) = read_entry(&path);
+        write_entry(
+            &path,
+            bad_sector.unwrap_or(sector),
+            bad_length.unwrap_or(length),
+        );
+
+        let mut region = RegionFile::open(&path).unwrap();
+        assert_eq!(region.read_chunk(IVec3::ZERO).unwrap(), None, "{}", name);
+        // Новий запис лягає відразу за заголовком, а не за пошкодженим сектором
+        region.write_chunk(IVec3::ZERO, &voxels).unwrap();
+        drop(region);
+        let file_len = std::fs::metadata(&path).unwrap().len();
+        assert!(
+            file_len < 1 << 20,
+            "{}: file grew to {} bytes",
+            name,
+            file_len
+        );
+        let mut region = RegionFile::open(&path).unwrap();
+        assert_eq!(
+            region.read_chunk(IVec3::ZERO).unwrap().as_ref(),
+            Some(&voxels),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn rewrite_never_overwrites_saved_copy() {
+    let path = temp_dir("rewrite").join("r.0.0.0.vxr");
+    let mut region = RegionFile::open(&path).unwrap();
+    let voxels = random_voxels(3);
+    region.write_chunk(IVec3::ZERO, &voxels).unwrap();
+    let (first, _) = read_entry(&path);
+
+    // Та сама довжина, але нова версія не лягає поверх тієї, на яку вказує таблиця
+    region.write_chunk(IVec3::ZERO, &voxels).unwrap();
+    let (second, _) = read_entry(&path);
+    assert_ne!(second, first);
+    region.write_chunk(IVec3::ZERO, &voxels).unwrap();
+    assert!(![first, second].contains(&read_entry(&path).0));
+
+    // Після flush сектори старих версій знову вільні, тож файл не росте
+    region.flush().unwrap();
+    let file_len = std::fs::metadata(&path).unwrap().len();
+    for _ in 0..10 {
+        region.write_chunk(IVec3::ZERO, &voxels).unwrap();
+        region.flush().unwrap();
+    }
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), file_len);
+    assert_eq!(
+        region.read_chunk(IVec3::ZERO).unwrap(),
+        Some(voxels.clone())
+    );
+
+    // Проміжки між записами знаходяться й після повторного відкриття
+    drop(region);
+    let mut region = RegionFile::open(&path).unwrap();
+    region.write_chunk(IVec3::X, &voxels).unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), file_len);
+    assert_eq!(region.read_chunk(IVec3::X).unwrap(), Some(voxels));
+}
+
+#[test]
+fn foreign_file_is_rejected() {
+    let path = temp_dir("foreign").join("r.0.0.0.vxr");
+    std::fs::write(&path, vec![0u8; 128 * 1024]).unwrap();
+    let err = RegionFile::open(&path).err().expect("not a region file");
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+fn sample_level() -> Level {
+    let mut settings = Settings::new();
+    settings.world_seed = 42;
+    settings.generator = GeneratorKind::Void;
+    let mut level = Level::new(&settings);
+    level.game_time = 123.5;
+    level.player.position = [1.5, 70.0, -8.25];
+    level.player.yaw = 0.75;
+    level.player.pitch = -0.5;
+    level
+}
+
+#[test]
+fn level_survives_save_and_load() {
+    let path = temp_dir("level").join("level.toml");
+    assert_eq!(
+        load_level(&path).unwrap(),
+        None,
+        "new world has no level file"
+    );
+    let level = sample_level();
+    save_level(&path, &level).unwrap();
+    assert_eq!(load_level(&path).unwrap(), Some(level.clone()));
+
+    let mut settings = Settings::new();
+    level.apply_to(&mut settings);
+    assert_eq!(settings.world_seed, 42);
+    assert_eq!(settings.generator, GeneratorKind::Void);
+}
+
+#[test]
+fn edited_level_fails_checksum() {
+    let path = temp_dir("level-checksum").join("level.toml");
+    save_level(&path, &sample_level()).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, text.replace("seed = 42", "seed = 43")).unwrap();
+    let err = load_level(&path).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().contains("checksum mismatch"), "{}", err);
+
+    // Без заголовка з сумою файл теж не приймається
+    let body = text.split_once('\n').unwrap().1;
+    std::fs::write(&path, body).unwrap();
+    let err = load_level(&path).unwrap_err();
+    assert!(err.to_string().contains("missing checksum"), "{}", err);
+}
@@ -0,0 +1,61 @@
+use glam::IVec3;
+use std::sync::Mutex;
+use std::sync::mpsc::{Receiver, Sender, channel};
+use voxel_engine::workers::WorkerPool;
+
+// Пул з одним робітником, який повертає завдання назад, але спершу повідомляє
+// про старт і чекає дозволу, тож тест керує тим, коли приходять результати
+fn gated_pool() -> (WorkerPool<u32, u32>, Receiver<u32>, Sender<()>) {
+    let (started_sender, started) = channel();
+    let (release, release_receiver) = channel::<()>();
+    let started_sender = Mutex::new(started_sender);
+    let release_receiver = Mutex::new(release_receiver);
+    let pool = WorkerPool::new("test", 1, move |_, job: u32| {
+        started_sender.lock().unwrap().send(job).unwrap();
+        release_receiver.lock().unwrap().recv().unwrap();
+        job
+    });
+    (pool, started, release)
+}
+
+#[test]
+fn cancelled_result_does_not_complete_newer_request() {
+    let (mut pool, started, release) = gated_pool();
+    let pos = IVec3::new(1, 0, -1);
+
+    pool.request(pos, 1);
+    assert_eq!(started.recv().unwrap(), 1);
+    pool.cancel(|_| false);
+    assert!(!pool.is_pending(pos));
+
+    // Перший результат ще в роботі, коли приходить новий запит на ту саму позицію
+    pool.request(pos, 2);
+    assert!(pool.is_pending(pos));
+    release.send(()).unwrap();
+    assert_eq!(started.recv().unwrap(), 2);
+    release.send(()).unwrap();
+
+    assert_eq!(pool.wait_finished(), Some((pos, 2)));
+    assert!(!pool.is_pending(pos));
+    assert_eq!(pool.wait_finished(), None);
+}
+
+#[test]
+fn queued_request_is_replaced() {
+    let (mut pool, started, release) = gated_pool();
+    let busy = IVec3::ZERO;
+    let pos = IVec3::X;
+
+    pool.request(busy, 1);
+    assert_eq!(started.recv().unwrap(), 1);
+    pool.request(pos, 2);
+    pool.request(pos, 3);
+    assert_eq!(pool.pending_count(), 2);
+
+    release.send(()).unwrap();
+    assert_eq!(pool.wait_finished(), Some((busy, 1)));
+    assert_eq!(started.recv().unwrap(), 3);
+    release.send(()).unwrap();
+    assert_eq!(pool.wait_finished(), Some((pos, 3)));
+    assert_eq!(pool.pending_count(), 0);
+}
@@ -0,0 +1,278 @@
+use glam::{IVec3, Vec3};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use voxel_engine::blocks::{AIR, BlockId, BlockRegistry};
+use voxel_engine::generators::WorldGenerator;
+use voxel_engine::settings::{CHUNK_SIZE, CHUNK_VOL, GeneratorKind, Settings, WorldHeight};
+use voxel_engine::world::World;
+
+// Порожній світ 3x3x3 чанки навколо нуля, уже згенерований і без брудних чанків
+fn void_world() -> World {
+    open_world(None)
+}
+
+fn test_settings() -> Settings {
+    let mut settings = Settings::new();
+    settings.render_distance = 1;
+    settings.generator = GeneratorKind::Void;
+    settings.generation_threads = 1;
+    settings.meshing_threads = 1;
+    settings
+}
+
+// З каталогом світ пише змінені чанки на диск і читає їх звідти
+fn open_world(dir: Option<&Path>) -> World {
+    let mut settings = test_settings();
+    settings.world_dir = dir.map(Path::to_path_buf);
+    let mut world = World::new(&settings, Arc::new(BlockRegistry::default()));
+    world.wait_for_generation();
+    world.wait_for_meshes();
+    world
+}
+
+fn dirty(world: &World) -> Vec<IVec3> {
+    let mut dirty: Vec<IVec3> = world
+        .chunks()
+        .filter(|chunk| chunk.is_dirty)
+        .map(|chunk| chunk.position)
+        .collect();
+    dirty.sort_by_key(|pos| pos.to_array());
+    dirty
+}
+
+#[test]
+fn set_voxel_marks_touched_neighbors_dirty() {
+    let mut world = void_world();
+    let stone = world.registry().id("stone").unwrap();
+    assert!(dirty(&world).is_empty());
+
+    // Усередині чанка змінюється лише він сам
+    assert!(world.set_voxel(IVec3::new(5, 5, 5), stone));
+    assert_eq!(world.get_voxel(IVec3::new(5, 5, 5)), Some(stone));
+    assert_eq!(dirty(&world), [IVec3::ZERO]);
+    world.wait_for_meshes();
+
+    // На межі з +X — ще й сусід з того боку
+    assert!(world.set_voxel(IVec3::new(31, 5, 5), stone));
+    assert_eq!(dirty(&world), [IVec3::ZERO, IVec3::X]);
+    world.wait_for_meshes();
+
+    // На межі з -X у чанку -X: сусід — центральний чанк
+    assert!(world.set_voxel(IVec3::new(-1, 5, 5), AIR));
+    assert!(dirty(&world).is_empty(), "nothing changed");
+    assert!(world.set_voxel(IVec3::new(-1, 5, 5), stone));
+    assert_eq!(dirty(&world), [IVec3::NEG_X, IVec3::ZERO]);
+}
+
+#[test]
+fn set_voxel_outside_loaded_chunks_fails() {
+    let mut world = void_world();
+    let stone = world.registry().id("stone").unwrap();
+    let far = IVec3::new(100, 0, 0);
+    assert_eq!(world.get_voxel(far), None);
+    assert!(!world.set_voxel(far, stone));
+}
+
+#[test]
+fn unloaded_chunk_keeps_changes() {
+    let dir = std::env::temp_dir().join(format!("voxel-world-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let pos = IVec3::new(5, 5, 5);
+    let mut world = open_world(Some(&dir));
+    let stone = world.registry().id("stone").unwrap();
+    assert!(world.set_voxel(pos, stone));
+
+    // Уходимо далеко й одразу повертаємось: запис ще може бути в черзі,
+    // але чанк не має повернутися з диска чи генератора без змін
+    world.update(Vec3::new(320.0, 16.0, 16.0), Vec3::NEG_Z);
+    assert_eq!(world.get_voxel(pos), None);
+    world.update(Vec3::splat(16.0), Vec3::NEG_Z);
+    world.wait_for_generation();
+    assert_eq!(world.get_voxel(pos), Some(stone));
+
+    // Після очікування запису зміна є і на диску
+    world.update(Vec3::new(320.0, 16.0, 16.0), Vec3::NEG_Z);
+    world.wait_for_saves().unwrap();
+    drop(world);
+    let world = open_world(Some(&dir));
+    assert_eq!(world.get_voxel(pos), Some(stone));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn diagonal_neighbor_change_updates_corner_ao() {
+    let mut world = void_world();
+    let stone = world.registry().id("stone").unwrap();
+    assert!(world.set_voxel(IVec3::new(31, 0, 31), stone));
+    world.wait_for_meshes();
+    world.take_meshes(usize::MAX);
+
+    // Кут верхньої грані в (32, 1, 32) межує лише з чанком (1, 0, 1) — через ребро
+    let corner_ao = |world: &mut World| {
+        let meshes = world.take_meshes(usize::MAX);
+        let (_, mesh) = meshes
+            .iter()
+            .find(|(pos, _)| *pos == IVec3::ZERO)
+            .expect("center chunk was remeshed");
+        mesh.opaque
+            .vertices()
+            .find(|vertex| vertex.face_id == 0 && vertex.position == [512, 16, 512])
+            .map(|vertex| vertex.ao)
+    };
+    assert!(world.set_voxel(IVec3::new(32, 1, 32), stone));
+    // Воксель на ребрі чанка (1, 0, 1) зачіпає обох сусідів через грані і діагонального
+    assert_eq!(
+        dirty(&world),
+        [IVec3::ZERO, IVec3::Z, IVec3::X, IVec3::new(1, 0, 1)]
+    );
+    world.wait_for_meshes();
+    assert_eq!(corner_ao(&mut world), Some(2));
+
+    assert!(world.set_voxel(IVec3::new(32, 1, 32), AIR));
+    world.wait_for_meshes();
+    assert_eq!(corner_ao(&mut world), Some(3));
+}
+
+#[test]
+fn top_layer_of_highest_chunk_has_top_faces() {
+    let mut world = void_world();
+    let stone = world.registry().id("stone").unwrap();
+    let WorldHeight::Bounded { max_y, .. } = world.world_height() else {
+        panic!("default world is bounded");
+    };
+    // Гравець піднімається, щоб найвищий ряд чанків завантажився
+    let top_chunk = IVec3::new(0, max_y, 0);
+    let top = (max_y + 1) * CHUNK_SIZE as i32 - 1;
+    world.update(Vec3::new(16.0, top as f32, 16.0), Vec3::NEG_Z);
+    world.wait_for_generation();
+    world.wait_for_meshes();
+    world.take_meshes(usize::MAX);
+
+    // Над стелею світу чанків немає, але там повітря, а не незавантажений камінь
+    assert!(world.set_voxel(IVec3::new(5, top, 5), stone));
+    world.wait_for_meshes();
+    let meshes = world.take_meshes(usize::MAX);
+    let (_, mesh) = meshes
+        .iter()
+        .find(|(pos, _)| *pos == top_chunk)
+        .expect("top chunk was remeshed");
+    let top_faces = mesh
+        .opaque
+        .vertices()
+        .step_by(4)
+        .filter(|vertex| vertex.face_id == 0)
+        .count();
+    assert_eq!(top_faces, 1);
+}
+
+// Суцільний камінь у чанках нижче y = 1, вище — повітря
+struct StoneBelow(BlockId);
+
+impl WorldGenerator for StoneBelow {
+    fn generate(&self, chunk_pos: IVec3) -> Vec<BlockId> {
+        let voxel_id = if chunk_pos.y < 1 { self.0 } else { AIR };
+        vec![voxel_id; CHUNK_VOL as usize]
+    }
+}
+
+#[test]
+fn empty_and_buried_chunks_are_not_meshed() {
+    let registry = Arc::new(BlockRegistry::default());
+    let stone = registry.id("stone").unwrap();
+    let mut world = World::with_generator(&test_settings(), registry, Arc::new(StoneBelow(stone)));
+    world.wait_for_generation();
+    world.wait_for_meshes();
+
+    // Ряд y = 1 — повітря, y = -1 закритий каменем і незавантаженими чанками (Solid),
+    // а в y = 0 над каменем повітря, тож там є верхні грані
+    let stats = world.stats();
+    assert_eq!(stats.empty_chunks, 9);
+    assert_eq!(stats.buried_chunks, 9);
+    for chunk in world.chunks() {
+        assert_eq!(chunk.skipped, chunk.position.y != 0, "{}", chunk.position);
+    }
+    for (chunk_pos, mesh) in world.take_meshes(usize::MAX) {
+        assert_eq!(mesh.opaque.is_empty(), chunk_pos.y != 0, "{}", chunk_pos);
+    }
+
+    // Яма в камені відкриває грані похованого чанка під нею
+    assert!(world.set_voxel(IVec3::new(5, -1, 5), AIR));
+    world.wait_for_meshes();
+    let stats = world.stats();
+    assert_eq!(stats.buried_chunks, 8);
+    assert!(!world.chunk(IVec3::NEG_Y).unwrap().skipped);
+}
+
+// Порожній світ, що запам'ятовує порядок генерації чанків
+#[derive(Default)]
+struct Recording(Mutex<Vec<IVec3>>);
+
+impl WorldGenerator for Recording {
+    fn generate(&self, chunk_pos: IVec3) -> Vec<BlockId> {
+        self.0.lock().unwrap().push(chunk_pos);
+        vec![AIR; CHUNK_VOL as usize]
+    }
+}
+
+#[test]
+fn closest_chunks_in_view_load_first() {
+    let generator = Arc::new(Recording::default());
+    // Один потік генерації бере запити в тому порядку, в якому World їх віддає
+    let mut world = World::with_generator(
+        &test_settings(),
+        Arc::new(BlockRegistry::default()),
+        generator.clone(),
+    );
+    // Гравець лишається в тому самому чанку, але повертається до +X: черга пересортовується
+    world.update(Vec3::splat(16.0), Vec3::X);
+    world.wait_for_generation();
+
+    let order = generator.0.lock().unwrap().clone();
+    assert_eq!(order.len(), 27);
+    let index = |chunk_pos: IVec3| order.iter().position(|pos| *pos == chunk_pos).unwrap();
+    assert_eq!(order[..2], [IVec3::ZERO, IVec3::X]);
+    // Ближчі раніше за дальші в тому самому напрямку
+    assert!(index(IVec3::X) < index(IVec3::new(1, 1, 1)));
+    assert!(index(IVec3::NEG_X) < index(IVec3::new(-1, -1, -1)));
+    // Перед камерою раніше, ніж на тій самій відстані позаду
+    for y in -1..=1 {
+        for z in -1..=1 {
+            assert!(index(IVec3::new(1, y, z)) < index(IVec3::new(-1, y, z)));
+        }
+    }
+    assert!(index(IVec3::Z) < index(IVec3::NEG_X));
+}
+
+fn loaded_layers(world: &World) -> Vec<i32> {
+    let mut layers: Vec<i32> = world.chunks().map(|chunk| chunk.position.y).collect();
+    layers.sort();
+    layers.dedup();
+    layers
+}
+
+#[test]
+fn cubic_world_streams_vertically() {
+    let mut settings = test_settings();
+    settings.world_height = WorldHeight::Cubic;
+    let mut world = World::new(&settings, Arc::new(BlockRegistry::default()));
+    world.wait_for_generation();
+    assert_eq!(loaded_layers(&world), [-1, 0, 1]);
+
+    // Далеко вгору, а потім униз: ряди чанків ідуть за гравцем, як по X і Z
+    for center_y in [20, -15] {
+        let player_pos = Vec3::new(16.0, (center_y * CHUNK_SIZE as i32 + 16) as f32, 16.0);
+        world.update(player_pos, Vec3::NEG_Z);
+        world.wait_for_generation();
+        assert_eq!(
+            loaded_layers(&world),
+            [center_y - 1, center_y, center_y + 1]
+        );
+        assert_eq!(world.chunks().count(), 27);
+    }
+
+    // В обмеженому світі над стелею нічого не завантажується
+    let mut world = void_world();
+    world.update(Vec3::new(16.0, 20.0 * CHUNK_SIZE as f32, 16.0), Vec3::NEG_Z);
+    world.wait_for_generation();
+    assert_eq!(world.chunks().count(), 0);
+}